@@ -121,6 +121,7 @@ use UnderflowResult::*;
 /// let stat = player_stats.entry("attack").or_insert(100);
 /// *stat += random_stat_buff();
 /// ```
+#[cfg_attr(not(test), rustc_diagnostic_item = "btreemap_type")]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct BTreeMap<K, V> {
     root: Option<node::Root<K, V>>,
@@ -57,6 +57,7 @@ use super::Recover;
 /// }
 /// ```
 #[derive(Hash, PartialEq, Eq, Ord, PartialOrd)]
+#[cfg_attr(not(test), rustc_diagnostic_item = "btreeset_type")]
 #[stable(feature = "rust1", since = "1.0.0")]
 pub struct BTreeSet<T> {
     map: BTreeMap<T, ()>,
@@ -228,6 +228,7 @@ use crate::ptr;
 ///
 /// See the [module-level documentation](index.html) for more.
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "cell_type")]
 #[repr(transparent)]
 pub struct Cell<T: ?Sized> {
     value: UnsafeCell<T>,
@@ -571,6 +572,7 @@ impl<T> Cell<[T]> {
 ///
 /// See the [module-level documentation](index.html) for more.
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "refcell_type")]
 pub struct RefCell<T: ?Sized> {
     borrow: Cell<BorrowFlag>,
     value: UnsafeCell<T>,
@@ -23,6 +23,7 @@ fn _assert_is_object_safe(_: &dyn Iterator<Item = ()>) {}
 ///
 /// [module-level documentation]: index.html
 /// [impl]: index.html#implementing-iterator
+#[rustc_diagnostic_item = "Iterator"]
 #[stable(feature = "rust1", since = "1.0.0")]
 #[rustc_on_unimplemented(
     on(
@@ -217,6 +217,7 @@ use crate::mem::ManuallyDrop;
 #[stable(feature = "maybe_uninit", since = "1.36.0")]
 // Lang item so we can wrap other types in it. This is useful for generators.
 #[lang = "maybe_uninit"]
+#[rustc_diagnostic_item = "MaybeUninit"]
 #[derive(Copy)]
 #[repr(transparent)]
 pub union MaybeUninit<T> {
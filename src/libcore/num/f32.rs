@@ -382,6 +382,7 @@ impl f32 {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[cfg_attr(not(bootstrap), allow(self_comparisons))]
     pub fn is_nan(self) -> bool {
         self != self
     }
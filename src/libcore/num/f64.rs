@@ -381,6 +381,7 @@ impl f64 {
     /// ```
     #[stable(feature = "rust1", since = "1.0.0")]
     #[inline]
+    #[cfg_attr(not(bootstrap), allow(self_comparisons))]
     pub fn is_nan(self) -> bool {
         self != self
     }
@@ -142,6 +142,7 @@ pub fn build_unchecked_lshift<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     let rhs = base::cast_shift_expr_rhs(bx, hir::BinOpKind::Shl, lhs, rhs);
     // #1877, #10183: Ensure that input is always valid
     let rhs = shift_mask_rhs(bx, rhs);
+    let rhs = splat_shift_rhs(bx, lhs, rhs);
     bx.shl(lhs, rhs)
 }
 
@@ -154,10 +155,27 @@ pub fn build_unchecked_rshift<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     let rhs = base::cast_shift_expr_rhs(bx, hir::BinOpKind::Shr, lhs, rhs);
     // #1877, #10183: Ensure that input is always valid
     let rhs = shift_mask_rhs(bx, rhs);
-    let is_signed = lhs_t.is_signed();
+    let rhs = splat_shift_rhs(bx, lhs, rhs);
+    let is_signed =
+        if lhs_t.is_simd() { lhs_t.simd_type(bx.tcx()).is_signed() } else { lhs_t.is_signed() };
     if is_signed { bx.ashr(lhs, rhs) } else { bx.lshr(lhs, rhs) }
 }
 
+/// Shifting a SIMD vector by a scalar amount shifts every lane by that amount.
+fn splat_shift_rhs<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
+    bx: &mut Bx,
+    lhs: Bx::Value,
+    rhs: Bx::Value,
+) -> Bx::Value {
+    let lhs_llty = bx.val_ty(lhs);
+    let rhs_llty = bx.val_ty(rhs);
+    if bx.type_kind(lhs_llty) == TypeKind::Vector && bx.type_kind(rhs_llty) != TypeKind::Vector {
+        bx.vector_splat(bx.vector_length(lhs_llty), rhs)
+    } else {
+        rhs
+    }
+}
+
 fn shift_mask_rhs<'a, 'tcx, Bx: BuilderMethods<'a, 'tcx>>(
     bx: &mut Bx,
     rhs: Bx::Value,
@@ -574,6 +574,9 @@ declare_features! (
     /// No longer treat an unsafe function as an unsafe block.
     (active, unsafe_block_in_unsafe_fn, "1.45.0", Some(71668), None),
 
+    /// Allows shifting every lane of a `#[repr(simd)]` integer vector by a scalar amount.
+    (active, simd_shift_by_scalar, "1.45.0", None, None),
+
     // -------------------------------------------------------------------------
     // feature-group-end: actual feature gates
     // -------------------------------------------------------------------------
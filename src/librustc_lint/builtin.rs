@@ -31,7 +31,7 @@ use rustc_errors::{Applicability, DiagnosticBuilder};
 use rustc_feature::{deprecated_attributes, AttributeGate, AttributeTemplate, AttributeType};
 use rustc_feature::{GateIssue, Stability};
 use rustc_hir as hir;
-use rustc_hir::def::{DefKind, Res};
+use rustc_hir::def::{CtorKind, DefKind, Res};
 use rustc_hir::def_id::DefId;
 use rustc_hir::{GenericParamKind, PatKind};
 use rustc_hir::{HirIdSet, Node};
@@ -2049,3 +2049,73 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for InvalidValue {
         }
     }
 }
+
+declare_lint! {
+    pub UNDOCUMENTED_NEWTYPE_NEG,
+    Allow,
+    "implementations of `Neg` for a newtype whose field cannot be negated, without documentation"
+}
+
+declare_lint_pass!(UndocumentedNewtypeNeg => [UNDOCUMENTED_NEWTYPE_NEG]);
+
+impl<'a, 'tcx> LateLintPass<'a, 'tcx> for UndocumentedNewtypeNeg {
+    fn check_item(&mut self, cx: &LateContext<'a, 'tcx>, item: &'tcx hir::Item<'tcx>) {
+        let (trait_ref, self_ty, items) = match item.kind {
+            hir::ItemKind::Impl { of_trait: Some(ref trait_ref), self_ty, items, .. } => {
+                (trait_ref, self_ty, items)
+            }
+            _ => return,
+        };
+        if item.span.from_expansion()
+            || trait_ref.trait_def_id().is_none()
+            || trait_ref.trait_def_id() != cx.tcx.lang_items().neg_trait()
+        {
+            return;
+        }
+
+        // Documentation on the impl or on its `neg` is what this lint asks for.
+        let is_doc = |attr: &ast::Attribute| attr.is_doc_comment() || attr.check_name(sym::doc);
+        if item.attrs.iter().any(is_doc)
+            || items
+                .iter()
+                .any(|item_ref| cx.tcx.hir().impl_item(item_ref.id).attrs.iter().any(is_doc))
+        {
+            return;
+        }
+
+        let impl_def_id = cx.tcx.hir().local_def_id(item.hir_id);
+        let ty = cx.tcx.type_of(impl_def_id);
+        let (adt, substs) = match ty.kind {
+            ty::Adt(adt, substs) if adt.is_struct() => (adt, substs),
+            _ => return,
+        };
+        let variant = adt.non_enum_variant();
+        if variant.ctor_kind != CtorKind::Fn || variant.fields.len() != 1 {
+            return;
+        }
+        let field = &variant.fields[0];
+        let field_ty = field.ty(cx.tcx, substs);
+        if field_ty.references_error() {
+            return;
+        }
+        let param_env = cx.tcx.param_env(impl_def_id);
+        let neg_trait = trait_ref.trait_def_id().unwrap();
+        if cx.tcx.type_implements_trait((neg_trait, field_ty, ty::List::empty(), param_env)) {
+            return;
+        }
+
+        cx.struct_span_lint(UNDOCUMENTED_NEWTYPE_NEG, self_ty.span, |lint| {
+            let mut err =
+                lint.build(&format!("`{}` implements `Neg`, but the type it wraps does not", ty));
+            if let Some(field_span) = cx.tcx.hir().span_if_local(field.did) {
+                err.span_label(field_span, format!("`{}` cannot be negated", field_ty));
+            }
+            err.help(&format!(
+                "consider documenting what negating a `{}` means, as it does not follow from \
+                 negating the wrapped value",
+                ty
+            ));
+            err.emit();
+        });
+    }
+}
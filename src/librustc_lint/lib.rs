@@ -187,6 +187,7 @@ macro_rules! late_lint_mod_passes {
                 UnreachablePub: UnreachablePub,
                 ExplicitOutlivesRequirements: ExplicitOutlivesRequirements,
                 InvalidValue: InvalidValue,
+                UndocumentedNewtypeNeg: UndocumentedNewtypeNeg,
             ]
         );
     };
@@ -28,6 +28,12 @@ declare_lint! {
     "comparisons made useless by limits of the types involved"
 }
 
+declare_lint! {
+    UNSIGNED_GREATER_THAN_ZERO,
+    Allow,
+    "`> 0` comparisons of unsigned values, which only check that the value is not zero"
+}
+
 declare_lint! {
     OVERFLOWING_LITERALS,
     Deny,
@@ -46,7 +52,11 @@ pub struct TypeLimits {
     negated_expr_id: Option<hir::HirId>,
 }
 
-impl_lint_pass!(TypeLimits => [UNUSED_COMPARISONS, OVERFLOWING_LITERALS]);
+impl_lint_pass!(TypeLimits => [
+    UNUSED_COMPARISONS,
+    UNSIGNED_GREATER_THAN_ZERO,
+    OVERFLOWING_LITERALS,
+]);
 
 impl TypeLimits {
     pub fn new() -> TypeLimits {
@@ -391,6 +401,14 @@ fn lint_literal<'a, 'tcx>(
     }
 }
 
+/// What comparing an unsigned value with the literal `0` amounts to.
+enum UnsignedZeroComparison {
+    /// `x >= 0` and `x < 0`, which are always `true` and `false` respectively.
+    Constant(bool),
+    /// `x > 0`, which is the same as `x != 0`.
+    NonZero,
+}
+
 impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeLimits {
     fn check_expr(&mut self, cx: &LateContext<'a, 'tcx>, e: &'tcx hir::Expr<'tcx>) {
         match e.kind {
@@ -401,10 +419,50 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeLimits {
                 }
             }
             hir::ExprKind::Binary(binop, ref l, ref r) => {
-                if is_comparison(binop) && !check_limits(cx, binop, &l, &r) {
-                    cx.struct_span_lint(UNUSED_COMPARISONS, e.span, |lint| {
-                        lint.build("comparison is useless due to type limits").emit()
-                    });
+                if !is_comparison(binop) {
+                    return;
+                }
+                match unsigned_zero_comparison(cx, binop, &l, &r) {
+                    Some((UnsignedZeroComparison::Constant(value), ty)) => {
+                        cx.struct_span_lint(UNUSED_COMPARISONS, e.span, |lint| {
+                            lint.build("comparison is useless due to type limits")
+                                .span_label(e.span, format!("always `{}`", value))
+                                .note(&format!(
+                                    "`{}` is unsigned, so it is never less than `0`",
+                                    ty
+                                ))
+                                .emit()
+                        });
+                    }
+                    Some((UnsignedZeroComparison::NonZero, ty)) => {
+                        cx.struct_span_lint(UNSIGNED_GREATER_THAN_ZERO, e.span, |lint| {
+                            let mut err = lint.build(&format!(
+                                "`{}` is unsigned, so this comparison only checks that it \
+                                 isn't `0`",
+                                ty
+                            ));
+                            let source_map = cx.sess().source_map();
+                            let snippets = (
+                                source_map.span_to_snippet(l.span),
+                                source_map.span_to_snippet(r.span),
+                            );
+                            if let (Ok(l), Ok(r)) = snippets {
+                                err.span_suggestion(
+                                    e.span,
+                                    "use `!=` to make this clear",
+                                    format!("{} != {}", l, r),
+                                    Applicability::MachineApplicable,
+                                );
+                            }
+                            err.emit()
+                        });
+                    }
+                    None if !check_limits(cx, binop, &l, &r) => {
+                        cx.struct_span_lint(UNUSED_COMPARISONS, e.span, |lint| {
+                            lint.build("comparison is useless due to type limits").emit()
+                        });
+                    }
+                    None => {}
                 }
             }
             hir::ExprKind::Lit(ref lit) => lint_literal(cx, self, e, lit),
@@ -435,6 +493,31 @@ impl<'a, 'tcx> LateLintPass<'a, 'tcx> for TypeLimits {
             )
         }
 
+        /// Recognizes comparisons of an unsigned value with the literal `0`, in either order.
+        fn unsigned_zero_comparison<'tcx>(
+            cx: &LateContext<'_, 'tcx>,
+            binop: hir::BinOp,
+            l: &hir::Expr<'_>,
+            r: &hir::Expr<'_>,
+        ) -> Option<(UnsignedZeroComparison, Ty<'tcx>)> {
+            let (lit, expr, swap) = match (&l.kind, &r.kind) {
+                (&hir::ExprKind::Lit(ref lit), _) => (lit, r, true),
+                (_, &hir::ExprKind::Lit(ref lit)) => (lit, l, false),
+                _ => return None,
+            };
+            let ty = cx.tables.node_type(expr.hir_id);
+            if !matches!(ty.kind, ty::Uint(_)) || !matches!(lit.node, ast::LitKind::Int(0, _)) {
+                return None;
+            }
+            let norm_binop = if swap { rev_binop(binop) } else { binop };
+            match norm_binop.node {
+                hir::BinOpKind::Ge => Some((UnsignedZeroComparison::Constant(true), ty)),
+                hir::BinOpKind::Lt => Some((UnsignedZeroComparison::Constant(false), ty)),
+                hir::BinOpKind::Gt => Some((UnsignedZeroComparison::NonZero, ty)),
+                _ => None,
+            }
+        }
+
         fn check_limits(
             cx: &LateContext<'_, '_>,
             binop: hir::BinOp,
@@ -3,10 +3,11 @@
 use crate::ty::sty::InferTy;
 use crate::ty::TyKind::*;
 use crate::ty::{TyCtxt, TyS};
-use rustc_errors::{Applicability, DiagnosticBuilder};
+use rustc_errors::{Applicability, Diagnostic, DiagnosticBuilder};
 use rustc_hir as hir;
 use rustc_hir::def_id::DefId;
 use rustc_hir::{QPath, TyKind, WhereBoundPredicate, WherePredicate};
+use rustc_span::Span;
 
 impl<'tcx> TyS<'tcx> {
     /// Similar to `TyS::is_primitive`, but also considers inferred numeric values to be primitive.
@@ -89,24 +90,94 @@ pub fn suggest_constraining_type_param(
         return false;
     };
 
-    const MSG_RESTRICT_BOUND_FURTHER: &str = "consider further restricting this bound";
-    let msg_restrict_type = format!("consider restricting type parameter `{}`", param_name);
-    let msg_restrict_type_further =
-        format!("consider further restricting type parameter `{}`", param_name);
-
     if def_id == tcx.lang_items().sized_trait() {
         // Type parameters are already `Sized` by default.
         err.span_label(param.span, &format!("this type parameter needs to be `{}`", constraint));
         return true;
     }
-    let mut suggest_restrict = |span| {
-        err.span_suggestion_verbose(
-            span,
-            MSG_RESTRICT_BOUND_FURTHER,
-            format!(" + {}", constraint),
-            Applicability::MachineApplicable,
-        );
-    };
+
+    let (span, suggestion, msg) = constraint_suggestion(generics, param, param_name, constraint);
+    err.span_suggestion_verbose(span, &msg, suggestion, Applicability::MachineApplicable);
+    true
+}
+
+/// Suggest restricting several type params at once, with a single suggestion containing all the
+/// new bounds. Each param is restricted the same way `suggest_constraining_type_param` would.
+///
+/// Returns `false` if none of the params could be found in `generics`.
+pub fn suggest_constraining_type_params<'a>(
+    generics: &hir::Generics<'_>,
+    err: &mut Diagnostic,
+    param_names_and_constraints: impl Iterator<Item = (&'a str, &'a str)>,
+) -> bool {
+    // Several constraints on the same param are suggested as a single bound.
+    let mut grouped: Vec<(&str, Vec<&str>)> = vec![];
+    for (param_name, constraint) in param_names_and_constraints {
+        match grouped.iter_mut().find(|(name, _)| *name == param_name) {
+            Some((_, constraints)) => constraints.push(constraint),
+            None => grouped.push((param_name, vec![constraint])),
+        }
+    }
+
+    let mut suggestions: Vec<(Span, String, String)> = vec![];
+    let mut param_names = vec![];
+    for (param_name, constraints) in grouped {
+        let param = match generics.params.iter().find(|p| p.name.ident().as_str() == param_name) {
+            Some(param) => param,
+            None => continue,
+        };
+        let constraint = constraints.join(" + ");
+        let (span, suggestion, msg) =
+            constraint_suggestion(generics, param, param_name, &constraint);
+        // Several new `where` clause predicates are all inserted at the end of the clause.
+        match suggestions.iter_mut().find(|(sp, ..)| *sp == span) {
+            Some((_, existing, _)) => existing.push_str(&suggestion),
+            None => suggestions.push((span, suggestion, msg)),
+        }
+        param_names.push(format!("`{}`", param_name));
+    }
+
+    match &mut suggestions[..] {
+        [] => return false,
+        [(span, suggestion, msg)] if param_names.len() == 1 => {
+            err.span_suggestion_verbose(
+                *span,
+                msg,
+                std::mem::take(suggestion),
+                Applicability::MachineApplicable,
+            );
+        }
+        _ => {
+            let last = param_names.pop().unwrap();
+            err.multipart_suggestion(
+                &format!(
+                    "consider restricting type parameters {} and {}",
+                    param_names.join(", "),
+                    last
+                ),
+                suggestions.into_iter().map(|(span, suggestion, _)| (span, suggestion)).collect(),
+                Applicability::MachineApplicable,
+            );
+        }
+    }
+    true
+}
+
+/// Computes where and how `param` can be restricted with `constraint`, returning the span to
+/// insert at, the text to insert and a message describing the suggestion.
+fn constraint_suggestion(
+    generics: &hir::Generics<'_>,
+    param: &hir::GenericParam<'_>,
+    param_name: &str,
+    constraint: &str,
+) -> (Span, String, String) {
+    const MSG_RESTRICT_BOUND_FURTHER: &str = "consider further restricting this bound";
+    let msg_restrict_type = format!("consider restricting type parameter `{}`", param_name);
+    let msg_restrict_type_further =
+        format!("consider further restricting type parameter `{}`", param_name);
+
+    let restrict =
+        |span: Span| (span, format!(" + {}", constraint), MSG_RESTRICT_BOUND_FURTHER.to_string());
 
     if param_name.starts_with("impl ") {
         // If there's an `impl Trait` used in argument position, suggest
@@ -124,8 +195,7 @@ pub fn suggest_constraining_type_param(
         //             |
         //             replace with: `impl Foo + Bar`
 
-        suggest_restrict(param.span.shrink_to_hi());
-        return true;
+        return restrict(param.span.shrink_to_hi());
     }
 
     if generics.where_clause.predicates.is_empty()
@@ -147,21 +217,14 @@ pub fn suggest_constraining_type_param(
             //          --
             //          |
             //          replace with: `T: Bar +`
-            suggest_restrict(bounds_span.shrink_to_hi());
+            restrict(bounds_span.shrink_to_hi())
         } else {
             // If user hasn't provided any bounds, suggest adding a new one:
             //
             //   fn foo<T>(t: T) { ... }
             //          - help: consider restricting this type parameter with `T: Foo`
-            err.span_suggestion_verbose(
-                param.span.shrink_to_hi(),
-                &msg_restrict_type,
-                format!(": {}", constraint),
-                Applicability::MachineApplicable,
-            );
+            (param.span.shrink_to_hi(), format!(": {}", constraint), msg_restrict_type)
         }
-
-        true
     } else {
         // This part is a bit tricky, because using the `where` clause user can
         // provide zero, one or many bounds for the same type parameter, so we
@@ -221,18 +284,13 @@ pub fn suggest_constraining_type_param(
         }
 
         match &param_spans[..] {
-            &[&param_span] => suggest_restrict(param_span.shrink_to_hi()),
-            _ => {
-                err.span_suggestion_verbose(
-                    generics.where_clause.tail_span_for_suggestion(),
-                    &msg_restrict_type_further,
-                    format!(", {}: {}", param_name, constraint),
-                    Applicability::MachineApplicable,
-                );
-            }
+            &[&param_span] => restrict(param_span.shrink_to_hi()),
+            _ => (
+                generics.where_clause.tail_span_for_suggestion(),
+                format!(", {}: {}", param_name, constraint),
+                msg_restrict_type_further,
+            ),
         }
-
-        true
     }
 }
 
@@ -13,6 +13,7 @@ use rustc_middle::mir::{
 use rustc_middle::ty::{self, suggest_constraining_type_param, Ty};
 use rustc_span::source_map::DesugaringKind;
 use rustc_span::Span;
+use rustc_trait_selection::traits;
 
 use crate::dataflow::drop_flag_effects;
 use crate::dataflow::indexes::{MoveOutIndex, MovePathIndex};
@@ -124,6 +125,7 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
             self.add_moved_or_invoked_closure_note(location, used_place, &mut err);
 
             let mut is_loop_move = false;
+            let mut is_operator_move = false;
             let is_partial_move = move_site_vec.iter().any(|move_site| {
                 let move_out = self.move_data.moves[(*move_site).moi];
                 let moved_place = &self.move_data.move_paths[move_out.path].place;
@@ -167,6 +169,13 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
                         );
                     }
                 }
+                self.suggest_cloning_op_assign_operand(
+                    &mut err,
+                    moved_place.as_ref(),
+                    move_out.source,
+                    move_span,
+                );
+                is_operator_move |= self.is_overloaded_op_call(move_out.source);
             }
 
             use_spans.var_span_label(
@@ -233,6 +242,9 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
                     None
                 };
                 self.note_type_does_not_implement_copy(&mut err, &note_msg, ty, span);
+                if is_operator_move {
+                    self.suggest_deriving_copy(&mut err, ty);
+                }
             }
 
             if let Some((_, mut old_err)) =
@@ -244,6 +256,202 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
         }
     }
 
+    /// Suggests `b.clone()` when the moved value is the right-hand side of an
+    /// overloaded compound assignment like `a += b` and implements `Clone`.
+    fn suggest_cloning_op_assign_operand(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        moved_place: PlaceRef<'tcx>,
+        move_location: Location,
+        move_span: Span,
+    ) {
+        let tcx = self.infcx.tcx;
+        let expr_span = match self.overloaded_op_assign_span(move_location) {
+            Some((_, span)) => span,
+            None => return,
+        };
+
+        // Only the right-hand side is moved into the call; it ends where the
+        // whole `a op= b` expression ends.
+        if move_span == expr_span
+            || !expr_span.contains(move_span)
+            || move_span.hi() != expr_span.hi()
+            || move_span.from_expansion()
+        {
+            return;
+        }
+
+        let clone_trait = match tcx.lang_items().clone_trait() {
+            Some(def_id) => def_id,
+            None => return,
+        };
+        let ty = Place::ty_from(moved_place.local, moved_place.projection, self.body, tcx).ty;
+        let param_env = tcx.param_env(self.mir_def_id.to_def_id());
+        if traits::type_known_to_meet_bound_modulo_regions(
+            &self.infcx,
+            param_env,
+            ty,
+            clone_trait,
+            move_span,
+        ) {
+            err.span_suggestion_verbose(
+                move_span.shrink_to_hi(),
+                "consider cloning the value if the performance cost is acceptable",
+                ".clone()".to_string(),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Suggests `#[derive(Copy, Clone)]` on a local type that was moved into an
+    /// overloaded operator, when all of its fields are `Copy` already.
+    fn suggest_deriving_copy(&self, err: &mut DiagnosticBuilder<'_>, ty: Ty<'tcx>) {
+        let tcx = self.infcx.tcx;
+        let def = match ty.kind {
+            ty::Adt(def, _) if def.did.is_local() => def,
+            _ => return,
+        };
+        let param_env = tcx.param_env(self.mir_def_id.to_def_id());
+        if traits::misc::can_type_implement_copy(tcx, param_env, ty).is_err() {
+            return;
+        }
+        let clone_trait = match tcx.lang_items().clone_trait() {
+            Some(def_id) => def_id,
+            None => return,
+        };
+        let def_span = tcx.def_span(def.did);
+        let derives = if traits::type_known_to_meet_bound_modulo_regions(
+            &self.infcx,
+            param_env,
+            ty,
+            clone_trait,
+            def_span,
+        ) {
+            "Copy"
+        } else {
+            "Copy, Clone"
+        };
+        let indent = tcx.sess.source_map().span_to_margin(def_span).unwrap_or(0);
+        err.span_suggestion_verbose(
+            def_span.shrink_to_lo(),
+            &format!(
+                "consider annotating `{}` with `#[derive({})]`",
+                tcx.def_path_str(def.did),
+                derives,
+            ),
+            format!("#[derive({})]\n{}", derives, " ".repeat(indent)),
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    /// Returns `true` if `location` is the call of an overloaded binary, unary
+    /// or compound assignment operator.
+    fn is_overloaded_op_call(&self, location: Location) -> bool {
+        let tcx = self.infcx.tcx;
+        if self.overloaded_op_assign_span(location).is_some() {
+            return true;
+        }
+        let terminator = self.body[location.block].terminator();
+        let func = match terminator.kind {
+            TerminatorKind::Call { ref func, from_hir_call: false, .. } => func,
+            _ => return false,
+        };
+        let trait_def_id = match func.ty(self.body, tcx).kind {
+            ty::FnDef(def_id, _) => tcx.trait_of_item(def_id),
+            _ => None,
+        };
+        let lang_items = tcx.lang_items();
+        [
+            lang_items.add_trait(),
+            lang_items.sub_trait(),
+            lang_items.mul_trait(),
+            lang_items.div_trait(),
+            lang_items.rem_trait(),
+            lang_items.bitxor_trait(),
+            lang_items.bitand_trait(),
+            lang_items.bitor_trait(),
+            lang_items.shl_trait(),
+            lang_items.shr_trait(),
+            lang_items.neg_trait(),
+            lang_items.not_trait(),
+        ]
+        .iter()
+        .any(|def_id| trait_def_id.is_some() && *def_id == trait_def_id)
+    }
+
+    /// If `location` is the call of an overloaded compound assignment operator,
+    /// returns the operator and the span of the whole `a op= b` expression.
+    fn overloaded_op_assign_span(&self, location: Location) -> Option<(&'static str, Span)> {
+        let tcx = self.infcx.tcx;
+        let terminator = self.body[location.block].terminator();
+        let func = match terminator.kind {
+            TerminatorKind::Call { ref func, from_hir_call: false, .. } => func,
+            _ => return None,
+        };
+        let trait_def_id = match func.ty(self.body, tcx).kind {
+            ty::FnDef(def_id, _) => tcx.trait_of_item(def_id)?,
+            _ => return None,
+        };
+        let lang_items = tcx.lang_items();
+        let op_assign_traits = [
+            (lang_items.add_assign_trait(), "+="),
+            (lang_items.sub_assign_trait(), "-="),
+            (lang_items.mul_assign_trait(), "*="),
+            (lang_items.div_assign_trait(), "/="),
+            (lang_items.rem_assign_trait(), "%="),
+            (lang_items.bitxor_assign_trait(), "^="),
+            (lang_items.bitand_assign_trait(), "&="),
+            (lang_items.bitor_assign_trait(), "|="),
+            (lang_items.shl_assign_trait(), "<<="),
+            (lang_items.shr_assign_trait(), ">>="),
+        ];
+        op_assign_traits
+            .iter()
+            .find(|(def_id, _)| *def_id == Some(trait_def_id))
+            .map(|&(_, op)| (op, terminator.source_info.span))
+    }
+
+    /// Explains a conflict like `self.field += self.name()`: the right-hand side
+    /// is evaluated first, but the value it produces keeps `*self` borrowed when
+    /// `self.field` is finally borrowed mutably for the call.
+    fn note_op_assign_to_field_conflict(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        location: Location,
+        place: Place<'tcx>,
+        span: Span,
+        issued_borrow: &BorrowData<'tcx>,
+        issued_span: Span,
+    ) {
+        let (op, expr_span) = match self.overloaded_op_assign_span(location) {
+            Some(op) => op,
+            None => return,
+        };
+        let borrowed_place = issued_borrow.borrowed_place.as_ref();
+        if !matches!(issued_borrow.kind, BorrowKind::Shared)
+            || !matches!(place.projection.last(), Some(ProjectionElem::Field(..)))
+            || !borrowed_place.is_prefix_of(place.as_ref())
+            || !expr_span.contains(issued_span)
+            || issued_span.lo() < span.hi()
+            || expr_span.from_expansion()
+        {
+            return;
+        }
+
+        let field_desc = self.describe_any_place(place.as_ref());
+        let base_desc = self.describe_any_place(borrowed_place);
+        err.note(&format!(
+            "the right-hand side of `{}` is evaluated before {} is borrowed mutably, \
+             but the value it produces still borrows {}",
+            op, field_desc, base_desc,
+        ));
+        err.help(&format!(
+            "consider converting the right-hand side to an owned value before the assignment, \
+             or borrowing only the fields of {} it needs",
+            base_desc,
+        ));
+    }
+
     pub(in crate::borrow_check) fn report_move_out_while_borrowed(
         &mut self,
         location: Location,
@@ -532,6 +740,15 @@ impl<'cx, 'tcx> MirBorrowckCtxt<'cx, 'tcx> {
             ));
         }
 
+        self.note_op_assign_to_field_conflict(
+            &mut err,
+            location,
+            place,
+            span,
+            issued_borrow,
+            issued_span,
+        );
+
         explanation.add_explanation_to_diagnostic(
             self.infcx.tcx,
             &self.body,
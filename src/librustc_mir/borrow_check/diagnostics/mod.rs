@@ -634,7 +634,7 @@ pub(super) enum BorrowedContentSource<'tcx> {
     DerefMutableRef,
     DerefSharedRef,
     OverloadedDeref(Ty<'tcx>),
-    OverloadedIndex(Ty<'tcx>),
+    OverloadedIndex(Ty<'tcx>, Ty<'tcx>),
 }
 
 impl BorrowedContentSource<'tcx> {
@@ -652,7 +652,7 @@ impl BorrowedContentSource<'tcx> {
                 }
                 _ => format!("dereference of `{}`", ty),
             },
-            BorrowedContentSource::OverloadedIndex(ty) => format!("index of `{}`", ty),
+            BorrowedContentSource::OverloadedIndex(ty, _) => format!("index of `{}`", ty),
         }
     }
 
@@ -664,7 +664,7 @@ impl BorrowedContentSource<'tcx> {
             // Overloaded deref and index operators should be evaluated into a
             // temporary. So we don't need a description here.
             BorrowedContentSource::OverloadedDeref(_)
-            | BorrowedContentSource::OverloadedIndex(_) => None,
+            | BorrowedContentSource::OverloadedIndex(..) => None,
         }
     }
 
@@ -684,7 +684,7 @@ impl BorrowedContentSource<'tcx> {
                 }
                 _ => format!("a dereference of `{}`", ty),
             },
-            BorrowedContentSource::OverloadedIndex(ty) => format!("an index of `{}`", ty),
+            BorrowedContentSource::OverloadedIndex(ty, _) => format!("an index of `{}`", ty),
         }
     }
 
@@ -701,7 +701,10 @@ impl BorrowedContentSource<'tcx> {
                 } else if Some(trait_id) == lang_items.index_trait()
                     || Some(trait_id) == lang_items.index_mut_trait()
                 {
-                    Some(BorrowedContentSource::OverloadedIndex(substs.type_at(0)))
+                    Some(BorrowedContentSource::OverloadedIndex(
+                        substs.type_at(0),
+                        substs.type_at(1),
+                    ))
                 } else {
                     None
                 }
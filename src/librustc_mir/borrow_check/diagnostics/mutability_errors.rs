@@ -1,11 +1,12 @@
 use rustc_hir as hir;
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_hir::Node;
 use rustc_index::vec::Idx;
 use rustc_middle::mir::{self, ClearCrossCrate, Local, LocalInfo, Location};
 use rustc_middle::mir::{Mutability, Place, PlaceRef, ProjectionElem};
 use rustc_middle::ty::{self, Ty, TyCtxt};
 use rustc_span::source_map::DesugaringKind;
-use rustc_span::symbol::kw;
+use rustc_span::symbol::{kw, sym};
 use rustc_span::Span;
 
 use crate::borrow_check::diagnostics::BorrowedContentSource;
@@ -468,12 +469,10 @@ impl<'a, 'tcx> MirBorrowckCtxt<'a, 'tcx> {
                             ty,
                         ));
                     }
-                    Some(BorrowedContentSource::OverloadedIndex(ty)) => {
-                        err.help(&format!(
-                            "trait `IndexMut` is required to modify indexed content, \
-                                but it is not implemented for `{}`",
-                            ty,
-                        ));
+                    Some(BorrowedContentSource::OverloadedIndex(ty, index_ty)) => {
+                        if !self.suggest_map_index_insert(&mut err, ty, span) {
+                            self.note_index_without_index_mut(&mut err, ty, index_ty, span);
+                        }
                     }
                     _ => (),
                 }
@@ -579,6 +578,174 @@ impl<'a, 'tcx> MirBorrowckCtxt<'a, 'tcx> {
             }
         }
     }
+
+    /// Targeted error when assigning to an entry of a `HashMap` or `BTreeMap` of type `ty` through
+    /// indexing, which can't insert the entry, suggesting `insert` instead. Returns whether the
+    /// assignment at `span` was found.
+    fn suggest_map_index_insert(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        ty: Ty<'tcx>,
+        span: Span,
+    ) -> bool {
+        let tcx = self.infcx.tcx;
+        let map = match ty.kind {
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::hashmap_type, def.did) => "HashMap",
+            ty::Adt(def, _) if tcx.is_diagnostic_item(sym::btreemap_type, def.did) => "BTreeMap",
+            _ => return false,
+        };
+
+        struct AssignFinder<'tcx> {
+            span: Span,
+            assign: Option<&'tcx hir::Expr<'tcx>>,
+        }
+        impl<'tcx> Visitor<'tcx> for AssignFinder<'tcx> {
+            type Map = intravisit::ErasedMap<'tcx>;
+
+            fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+                NestedVisitorMap::None
+            }
+
+            fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+                // The assignment is reported at the span of its LHS if the old value is dropped.
+                match expr.kind {
+                    hir::ExprKind::Assign(lhs, _, _)
+                        if expr.span == self.span || lhs.span == self.span =>
+                    {
+                        self.assign = Some(expr);
+                    }
+                    _ => intravisit::walk_expr(self, expr),
+                }
+            }
+        }
+        let hir = tcx.hir();
+        let body = hir.body(hir.body_owned_by(hir.as_local_hir_id(self.mir_def_id)));
+        let mut finder = AssignFinder { span, assign: None };
+        finder.visit_body(body);
+        let assign = match finder.assign {
+            Some(assign) => assign,
+            None => return false,
+        };
+        let (base, index, rhs) = match assign.kind {
+            hir::ExprKind::Assign(
+                hir::Expr { kind: hir::ExprKind::Index(base, index), .. },
+                rhs,
+                _,
+            ) => (base, index, rhs),
+            _ => return false,
+        };
+
+        err.note(&format!(
+            "`{}` doesn't implement `IndexMut`, as indexing can't create the entry of a key that \
+             isn't in the map yet",
+            map,
+        ));
+        err.help(
+            "to modify the value of an entry, or insert it if it's missing, use `entry(..)`, \
+             e.g. with `.or_insert(..)`",
+        );
+        // The key is borrowed to index the map, while `insert` takes it by value.
+        let key = match index.kind {
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, _, key) => key,
+            _ => index,
+        };
+        let source_map = tcx.sess.source_map();
+        if let (Ok(base), Ok(key), Ok(rhs)) = (
+            source_map.span_to_snippet(base.span),
+            source_map.span_to_snippet(key.span),
+            source_map.span_to_snippet(rhs.span),
+        ) {
+            err.span_suggestion_verbose(
+                assign.span,
+                &format!("to insert the entry, or replace its value, use `{}::insert`", map),
+                format!("{}.insert({}, {})", base, key, rhs),
+                // Borrowed keys aren't always owned by value as `insert` wants them.
+                Applicability::MaybeIncorrect,
+            );
+        }
+        true
+    }
+
+    /// Explains that modifying the content of the index expression at `span` requires `ty` to
+    /// implement `IndexMut<index_ty>` on top of `Index<index_ty>`, and how it could be implemented
+    /// for a local type.
+    fn note_index_without_index_mut(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        ty: Ty<'tcx>,
+        index_ty: Ty<'tcx>,
+        span: Span,
+    ) {
+        struct IndexFinder<'tcx> {
+            span: Span,
+            base: Option<&'tcx hir::Expr<'tcx>>,
+        }
+        impl<'tcx> Visitor<'tcx> for IndexFinder<'tcx> {
+            type Map = intravisit::ErasedMap<'tcx>;
+
+            fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+                NestedVisitorMap::None
+            }
+
+            fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+                // The outermost indexing in the span is the one that needs `IndexMut`.
+                if self.base.is_some() {
+                    return;
+                }
+                match expr.kind {
+                    hir::ExprKind::Index(base, _) if self.span.contains(expr.span) => {
+                        self.base = Some(base);
+                    }
+                    _ => intravisit::walk_expr(self, expr),
+                }
+            }
+        }
+        let tcx = self.infcx.tcx;
+        let hir = tcx.hir();
+        let body = hir.body(hir.body_owned_by(hir.as_local_hir_id(self.mir_def_id)));
+        let mut finder = IndexFinder { span, base: None };
+        finder.visit_body(body);
+        if let Some(base) = finder.base {
+            err.span_label(base.span, format!("this is indexed as a `{}`", ty));
+        }
+
+        err.note(&format!(
+            "`{}` implements `Index<{}>`, but not `IndexMut<{1}>`, which is required to modify \
+             indexed content",
+            ty, index_ty,
+        ));
+        if let ty::Adt(def, _) = ty.kind {
+            if def.did.is_local() {
+                let missing_trait = format!("std::ops::IndexMut<{}>", index_ty);
+                err.note(&format!(
+                    "an implementation of `{}` might be missing for `{}`",
+                    missing_trait, ty,
+                ));
+                // Implement it for the type with its own parameters, rather than just for
+                // the instance at hand, unless one of them is a const parameter.
+                let generics = tcx.generics_of(def.did);
+                let (params, self_ty) = if generics.params.is_empty()
+                    || generics
+                        .params
+                        .iter()
+                        .any(|param| matches!(param.kind, ty::GenericParamDefKind::Const))
+                {
+                    (String::new(), ty.to_string())
+                } else {
+                    let params = generics.params.iter().map(|param| param.name.to_string());
+                    (
+                        format!("<{}>", params.collect::<Vec<_>>().join(", ")),
+                        tcx.type_of(def.did).to_string(),
+                    )
+                };
+                err.help(&format!(
+                    "an implementation could start with `impl{} {} for {} {{ fn index_mut(&mut \
+                     self, index: {}) -> &mut Self::Output {{ .. }} }}`",
+                    params, missing_trait, self_ty, index_ty,
+                ));
+            }
+        }
+    }
 }
 
 fn suggest_ampmut_self<'tcx>(
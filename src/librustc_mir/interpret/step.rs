@@ -155,7 +155,12 @@ impl<'mir, 'tcx: 'mir, M: Machine<'mir, 'tcx>> InterpCx<'mir, 'tcx, M> {
 
             BinaryOp(bin_op, ref left, ref right) => {
                 let layout = binop_left_homogeneous(bin_op).then_some(dest.layout);
-                let left = self.read_immediate(self.eval_operand(left, layout)?)?;
+                let left = self.eval_operand(left, layout)?;
+                // Shifts are the only builtin operation on SIMD vectors.
+                if matches!(bin_op, mir::BinOp::Shl | mir::BinOp::Shr) && left.layout.ty.is_simd() {
+                    throw_unsup_format!("shifting SIMD vectors is not supported");
+                }
+                let left = self.read_immediate(left)?;
                 let layout = binop_right_homogeneous(bin_op).then_some(left.layout);
                 let right = self.read_immediate(self.eval_operand(right, layout)?)?;
                 self.binop_ignore_overflow(bin_op, left, right, dest)?;
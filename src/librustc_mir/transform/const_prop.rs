@@ -498,6 +498,15 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
         right: &Operand<'tcx>,
         source_info: SourceInfo,
     ) -> Option<()> {
+        // Shifts by a literal amount are linted on during type-checking already.
+        if op == BinOp::Shr || op == BinOp::Shl {
+            if let Operand::Constant(c) = right {
+                if let ConstKind::Value(_) = c.literal.val {
+                    return Some(());
+                }
+            }
+        }
+
         let r =
             self.use_ecx(|this| this.ecx.read_immediate(this.ecx.eval_operand(right, None)?))?;
         // Check for exceeding shifts *even if* we cannot evaluate the LHS.
@@ -565,6 +574,12 @@ impl<'mir, 'tcx> ConstPropagator<'mir, 'tcx> {
                 trace!("checking UnaryOp(op = {:?}, arg = {:?})", op, arg);
                 self.check_unary_op(*op, arg, source_info)?;
             }
+            // The interpreter can't shift SIMD vectors, the only operation on them that is builtin.
+            Rvalue::BinaryOp(_, left, _) if left.ty(&self.local_decls, self.tcx).is_simd() => {
+                trace!("skipping BinaryOp on SIMD vector {:?}", left);
+
+                return None;
+            }
             Rvalue::BinaryOp(op, left, right) => {
                 trace!("checking BinaryOp(op = {:?}, left = {:?}, right = {:?})", op, left, right);
                 self.check_binary_op(*op, left, right, source_info)?;
@@ -595,6 +595,11 @@ impl<'tcx> Validator<'_, 'tcx> {
                     // raw pointer operations are not allowed inside promoteds
                     return Err(Unpromotable);
                 }
+
+                // SIMD vector shifts can't be evaluated at compile time
+                if lhs.ty(self.body, self.tcx).is_simd() {
+                    return Err(Unpromotable);
+                }
             }
 
             Rvalue::NullaryOp(NullOp::Box, _) => return Err(Unpromotable),
@@ -362,6 +362,14 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
 
                     block = self.assert(block, Operand::Move(of), false, overflow_err, span);
                 }
+            } else if self.hir.check_overflow()
+                && (op == BinOp::Shl || op == BinOp::Shr)
+                && ty.is_simd()
+            {
+                // Shifting every lane of an integer vector by a scalar amount
+                // (`#![feature(simd_shift_by_scalar)]`) overflows just like
+                // shifting a single integer of the lane type.
+                block = self.check_simd_shift_amount(block, op, span, ty, &rhs);
             }
 
             block.and(Rvalue::BinaryOp(op, lhs, rhs))
@@ -445,6 +453,54 @@ impl<'a, 'tcx> Builder<'a, 'tcx> {
         block.and(Operand::Move(Place::from(temp)))
     }
 
+    // Helper to assert that the scalar amount `rhs` that every lane of the
+    // integer vector `ty` is shifted by is smaller than the bit width of a lane.
+    fn check_simd_shift_amount(
+        &mut self,
+        block: BasicBlock,
+        op: BinOp,
+        span: Span,
+        ty: Ty<'tcx>,
+        rhs: &Operand<'tcx>,
+    ) -> BasicBlock {
+        let tcx = self.hir.tcx();
+        let source_info = self.source_info(span);
+        let bool_ty = self.hir.bool_ty();
+        let (_, elem_ty) = ty.simd_size_and_type(tcx);
+        if !elem_ty.is_integral() || !rhs.ty(&self.local_decls, tcx).is_integral() {
+            return block;
+        }
+
+        // Casting the amount to `u128` turns a negative amount into a huge
+        // one, so a single comparison catches both ways to overflow.
+        let amount = self.temp(tcx.types.u128, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            amount,
+            Rvalue::Cast(CastKind::Misc, rhs.to_copy(), tcx.types.u128),
+        );
+
+        let param_ty = ty::ParamEnv::empty().and(elem_ty);
+        let bits = tcx.layout_of(param_ty).unwrap().size.bits();
+        let bits = ty::Const::from_bits(
+            tcx,
+            u128::from(bits),
+            ty::ParamEnv::empty().and(tcx.types.u128),
+        );
+        let bits = self.literal_operand(span, bits);
+
+        let in_range = self.temp(bool_ty, span);
+        self.cfg.push_assign(
+            block,
+            source_info,
+            in_range,
+            Rvalue::BinaryOp(BinOp::Lt, Operand::Move(amount), bits),
+        );
+
+        self.assert(block, Operand::Move(in_range), true, AssertKind::Overflow(op), span)
+    }
+
     // Helper to get a `-1` value of the appropriate type
     fn neg_1_literal(&mut self, span: Span, ty: Ty<'tcx>) -> Operand<'tcx> {
         let param_ty = ty::ParamEnv::empty().and(ty);
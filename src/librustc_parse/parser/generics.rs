@@ -1,9 +1,10 @@
 use super::Parser;
 
 use rustc_ast::ast::{self, Attribute, GenericBounds, GenericParam, GenericParamKind, WhereClause};
+use rustc_ast::ptr::P;
 use rustc_ast::token;
-use rustc_errors::PResult;
-use rustc_span::symbol::{kw, sym};
+use rustc_errors::{Applicability, PResult};
+use rustc_span::symbol::{kw, sym, Ident};
 
 impl<'a> Parser<'a> {
     /// Parses bounds of a lifetime parameter `BOUND + BOUND + BOUND`, possibly with trailing `+`.
@@ -235,7 +236,10 @@ impl<'a> Parser<'a> {
         // or with mandatory equality sign and the second type.
         let ty = self.parse_ty()?;
         if self.eat(&token::Colon) {
-            let bounds = self.parse_generic_bounds(Some(self.prev_token.span))?;
+            let mut bounds = self.parse_generic_bounds(Some(self.prev_token.span))?;
+            if self.check(&token::Eq) || self.check(&token::EqEq) {
+                self.recover_op_trait_output_eq(&mut bounds)?;
+            }
             Ok(ast::WherePredicate::BoundPredicate(ast::WhereBoundPredicate {
                 span: lo.to(self.prev_token.span),
                 bound_generic_params: lifetime_defs,
@@ -257,6 +261,58 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Recovers from `T: Add == U` (or `T: Add = U`), meant to constrain the `Output` of an
+    /// operator trait, by parsing it as `T: Add<Output = U>`.
+    fn recover_op_trait_output_eq(&mut self, bounds: &mut GenericBounds) -> PResult<'a, ()> {
+        let segment = match &mut bounds[..] {
+            [ast::GenericBound::Trait(poly_trait_ref, ast::TraitBoundModifier::None)] => {
+                match poly_trait_ref.trait_ref.path.segments.last_mut() {
+                    Some(segment) if segment.args.is_none() => segment,
+                    _ => return Ok(()),
+                }
+            }
+            _ => return Ok(()),
+        };
+        let trait_name = segment.ident.as_str();
+        match &*trait_name {
+            "Add" | "Sub" | "Mul" | "Div" | "Rem" | "BitAnd" | "BitOr" | "BitXor" | "Shl"
+            | "Shr" | "Neg" | "Not" => {}
+            _ => return Ok(()),
+        }
+        let eq_span = self.token.span;
+        self.bump();
+        let ty = self.parse_ty()?;
+        let span = segment.ident.span.shrink_to_hi().to(ty.span);
+
+        let mut err = self.struct_span_err(
+            eq_span,
+            "equality constraints on operator traits must use the `Output` associated type",
+        );
+        err.span_label(eq_span, "not supported");
+        if let Ok(snippet) = self.span_to_snippet(ty.span) {
+            err.span_suggestion_verbose(
+                span,
+                &format!("if this is `std::ops::{}`, constrain its `Output` instead", trait_name),
+                format!("<Output = {}>", snippet),
+                // The trait is only recognized by its name.
+                Applicability::MaybeIncorrect,
+            );
+        }
+        err.emit();
+
+        let constraint = ast::AssocTyConstraint {
+            id: ast::DUMMY_NODE_ID,
+            ident: Ident::new(sym::Output, eq_span),
+            kind: ast::AssocTyConstraintKind::Equality { ty },
+            span,
+        };
+        segment.args = Some(P(ast::GenericArgs::AngleBracketed(ast::AngleBracketedArgs {
+            span,
+            args: vec![ast::AngleBracketedArg::Constraint(constraint)],
+        })));
+        Ok(())
+    }
+
     pub(super) fn choose_generics_over_qpath(&self, start: usize) -> bool {
         // There's an ambiguity between generic parameters and qualified paths in impls.
         // If we see `<` it may start both, so we have to inspect some following tokens.
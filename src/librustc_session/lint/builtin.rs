@@ -532,6 +532,94 @@ declare_lint! {
     "unsafe operations in unsafe functions without an explicit unsafe block are deprecated",
 }
 
+declare_lint! {
+    pub REPR_TRANSPARENT_EXTERNAL_NON_EXHAUSTIVE,
+    Warn,
+    "zero-sized fields of transparent types containing external `#[non_exhaustive]` types",
+    @future_incompatible = FutureIncompatibleInfo {
+        reference: "issue #78586 <https://github.com/rust-lang/rust/issues/78586>",
+        edition: None,
+    };
+}
+
+declare_lint! {
+    pub OP_ON_ZST,
+    Allow,
+    "arithmetic operators applied to zero-sized operands"
+}
+
+declare_lint! {
+    pub UNPREDICTABLE_FUNCTION_POINTER_COMPARISONS,
+    Warn,
+    "comparisons of function pointers, whose addresses are not guaranteed to be unique"
+}
+
+declare_lint! {
+    pub XOR_USED_AS_POW,
+    Warn,
+    "`^` applied to a small integer literal, where exponentiation was probably intended"
+}
+
+declare_lint! {
+    pub SELF_COMPARISONS,
+    Warn,
+    "comparisons of a place with itself, which are constant or a disguised NaN check"
+}
+
+declare_lint! {
+    pub NON_SHORT_CIRCUIT_BOOL_OPS,
+    Allow,
+    "`&` and `|` on booleans whose right-hand side makes calls that `&&` and `||` would skip"
+}
+
+declare_lint! {
+    pub NEGATED_EQUALITY_OPERANDS,
+    Warn,
+    "`!` on the left-hand side of `==` or `!=`, which looks like it negates the whole comparison"
+}
+
+declare_lint! {
+    pub DIVERGING_SHORT_CIRCUIT_OPERANDS,
+    Allow,
+    "`&&` and `||` whose right-hand side diverges, used for their value"
+}
+
+declare_lint! {
+    pub POTENTIAL_UNDERFLOW,
+    Allow,
+    "`x -= 1` on unsigned integers, which underflows if `x` is `0`"
+}
+
+declare_lint! {
+    pub OP_ASSOCIATIVITY,
+    Allow,
+    "chains of `-` or `/` without parentheses, which are evaluated from left to right"
+}
+
+declare_lint! {
+    pub NEGATIVE_SHIFT,
+    Warn,
+    "shifts by a negative integer literal, like `x << -1`"
+}
+
+declare_lint! {
+    pub IDENTITY_OPS,
+    Allow,
+    "operations with an identity element as an operand, which have no effect"
+}
+
+declare_lint! {
+    pub EQUALITY_CHAINS,
+    Allow,
+    "`||` chains comparing the same place with several values, which could use `matches!`"
+}
+
+declare_lint! {
+    pub BINARY_OP_METHOD_CALL_PARITY,
+    Allow,
+    "calls like `a.add(b)` of operator trait methods, which can be written as `a + b`"
+}
+
 declare_lint_pass! {
     /// Does nothing as a lint pass, but registers some `Lint`s
     /// that are used by other parts of the compiler.
@@ -604,6 +692,20 @@ declare_lint_pass! {
         INLINE_NO_SANITIZE,
         ASM_SUB_REGISTER,
         UNSAFE_OP_IN_UNSAFE_FN,
+        OP_ON_ZST,
+        REPR_TRANSPARENT_EXTERNAL_NON_EXHAUSTIVE,
+        UNPREDICTABLE_FUNCTION_POINTER_COMPARISONS,
+        XOR_USED_AS_POW,
+        SELF_COMPARISONS,
+        NON_SHORT_CIRCUIT_BOOL_OPS,
+        IDENTITY_OPS,
+        NEGATED_EQUALITY_OPERANDS,
+        DIVERGING_SHORT_CIRCUIT_OPERANDS,
+        POTENTIAL_UNDERFLOW,
+        OP_ASSOCIATIVITY,
+        NEGATIVE_SHIFT,
+        EQUALITY_CHAINS,
+        BINARY_OP_METHOD_CALL_PARITY,
     ]
 }
 
@@ -180,10 +180,13 @@ symbols! {
         box_syntax,
         braced_empty_structs,
         bswap,
+        btreemap_type,
+        btreeset_type,
         bitreverse,
         C,
         caller_location,
         cdylib,
+        cell_type,
         cfg,
         cfg_accessible,
         cfg_attr,
@@ -362,6 +365,8 @@ symbols! {
         Hash,
         HashSet,
         HashMap,
+        hashmap_type,
+        hashset_type,
         hexagon_target_feature,
         hidden,
         homogeneous_aggregate,
@@ -457,6 +462,7 @@ symbols! {
         may_dangle,
         maybe_uninit_uninit,
         maybe_uninit_zeroed,
+        MaybeUninit,
         mem_uninitialized,
         mem_zeroed,
         member_constraints,
@@ -477,6 +483,7 @@ symbols! {
         movbe_target_feature,
         mul_with_overflow,
         must_use,
+        mutex_type,
         naked,
         naked_functions,
         name,
@@ -530,6 +537,7 @@ symbols! {
         option,
         Option,
         option_env,
+        option_type,
         options,
         opt_out_copy,
         or,
@@ -555,6 +563,7 @@ symbols! {
         path,
         pattern_parentheses,
         Pending,
+        PhantomPinned,
         pin,
         Pin,
         pinned,
@@ -606,6 +615,7 @@ symbols! {
         reason,
         recursion_limit,
         reexport_test_harness_main,
+        refcell_type,
         reflect,
         register_attr,
         register_tool,
@@ -621,7 +631,9 @@ symbols! {
         re_rebalance_coherence,
         result,
         Result,
+        result_type,
         Return,
+        reverse_type,
         rhs,
         riscv_target_feature,
         rlib,
@@ -695,6 +707,7 @@ symbols! {
         rust_eh_personality,
         rust_oom,
         rvalue_static_promotion,
+        rwlock_type,
         sanitize,
         sanitizer_runtime,
         saturating_add,
@@ -708,6 +721,7 @@ symbols! {
         simd_extract,
         simd_ffi,
         simd_insert,
+        simd_shift_by_scalar,
         since,
         size,
         size_of,
@@ -728,6 +742,7 @@ symbols! {
         std,
         std_inject,
         str,
+        string_type,
         stringify,
         stmt,
         stmt_expr_attributes,
@@ -826,6 +841,8 @@ symbols! {
         var,
         vec,
         Vec,
+        vec_type,
+        vecdeque_type,
         version,
         vis,
         visible_private_types,
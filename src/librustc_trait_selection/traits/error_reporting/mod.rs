@@ -25,6 +25,7 @@ use rustc_middle::ty::{
     TypeFoldable, WithConstness,
 };
 use rustc_session::DiagnosticMessageId;
+use rustc_span::symbol::sym;
 use rustc_span::{ExpnKind, Span, DUMMY_SP};
 use std::fmt;
 
@@ -1022,6 +1023,12 @@ trait InferCtxtPrivExt<'tcx> {
         body_id: Option<hir::BodyId>,
     );
 
+    fn note_ambiguous_operator_rhs(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        data: &ty::PolyProjectionPredicate<'tcx>,
+    );
+
     fn predicate_can_apply(
         &self,
         param_env: ty::ParamEnv<'tcx>,
@@ -1583,6 +1590,7 @@ impl<'a, 'tcx> InferCtxtPrivExt<'tcx> for InferCtxt<'a, 'tcx> {
                         predicate,
                     );
                     err.span_label(span, &format!("cannot satisfy `{}`", predicate));
+                    self.note_ambiguous_operator_rhs(&mut err, data);
                     err
                 }
             }
@@ -1606,6 +1614,72 @@ impl<'a, 'tcx> InferCtxtPrivExt<'tcx> for InferCtxt<'a, 'tcx> {
         err.emit();
     }
 
+    /// Explains an ambiguous `Output` of a binary operator, e.g. in `let total = d + x.into();`,
+    /// when the left-hand side implements the operator for several right-hand side types: which
+    /// implementation, and so which `Output`, applies depends on the type of the right-hand side.
+    fn note_ambiguous_operator_rhs(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        data: &ty::PolyProjectionPredicate<'tcx>,
+    ) {
+        let tcx = self.tcx;
+        let projection_ty = data.skip_binder().projection_ty;
+        let trait_ref = projection_ty.trait_ref(tcx);
+        let lang_items = tcx.lang_items();
+        let op_traits = [
+            lang_items.add_trait(),
+            lang_items.sub_trait(),
+            lang_items.mul_trait(),
+            lang_items.div_trait(),
+            lang_items.rem_trait(),
+            lang_items.bitxor_trait(),
+            lang_items.bitand_trait(),
+            lang_items.bitor_trait(),
+            lang_items.shl_trait(),
+            lang_items.shr_trait(),
+        ];
+        if !op_traits.contains(&Some(trait_ref.def_id))
+            || tcx.associated_item(projection_ty.item_def_id).ident.name != sym::Output
+            || !trait_ref.substs.type_at(1).is_ty_var()
+        {
+            return;
+        }
+        let self_ty = trait_ref.self_ty();
+        let mut rhs_tys = vec![];
+        tcx.for_each_relevant_impl(trait_ref.def_id, self_ty, |impl_def_id| {
+            if let Some(impl_trait_ref) = tcx.impl_trait_ref(impl_def_id) {
+                let rhs_ty = impl_trait_ref.substs.type_at(1);
+                if impl_trait_ref.self_ty() == self_ty
+                    && !rhs_ty.needs_subst()
+                    && !rhs_tys.contains(&rhs_ty)
+                {
+                    rhs_tys.push(rhs_ty);
+                }
+            }
+        });
+        if rhs_tys.len() < 2 {
+            return;
+        }
+        let trait_path = tcx.def_path_str(trait_ref.def_id);
+        let mut impls: Vec<_> =
+            rhs_tys.iter().take(4).map(|ty| format!("`{}<{}>`", trait_path, ty)).collect();
+        let impls = if rhs_tys.len() > 4 {
+            format!("{}, and {} others", impls.join(", "), rhs_tys.len() - 4)
+        } else {
+            let last = impls.pop().unwrap();
+            format!("{} and {}", impls.join(", "), last)
+        };
+        err.note(&format!(
+            "`{}` implements {}, so the type of the right-hand side has to be known to tell \
+             which `Output` applies",
+            self_ty, impls,
+        ));
+        err.help(
+            "consider annotating the type of the right-hand side, e.g. by binding it to a \
+             variable with an explicit type first",
+        );
+    }
+
     /// Returns `true` if the trait predicate may apply for *some* assignment
     /// to the type parameters.
     fn predicate_can_apply(
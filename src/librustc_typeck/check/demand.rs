@@ -31,6 +31,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
         self.suggest_boxing_when_appropriate(err, expr, expected, expr_ty);
         self.suggest_missing_await(err, expr, expected, expr_ty);
+        self.note_const_binop_value(err, expr, expr_ty);
+        self.note_shift_result_type(err, expr, expected);
     }
 
     // Requires that the two types unify, and prints an error message if
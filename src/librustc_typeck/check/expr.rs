@@ -220,7 +220,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         match expr.kind {
             ExprKind::Box(ref subexpr) => self.check_expr_box(subexpr, expected),
             ExprKind::Lit(ref lit) => self.check_lit(&lit, expected),
-            ExprKind::Binary(op, ref lhs, ref rhs) => self.check_binop(expr, op, lhs, rhs),
+            ExprKind::Binary(op, ref lhs, ref rhs) => {
+                self.check_binop(expr, op, lhs, rhs, expected)
+            }
             ExprKind::Assign(ref lhs, ref rhs, ref span) => {
                 self.check_expr_assign(expr, expected, lhs, rhs, span)
             }
@@ -281,7 +283,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 self.check_expr_struct(expr, expected, qpath, fields, base_expr)
             }
             ExprKind::Field(ref base, field) => self.check_field(expr, needs, &base, field),
-            ExprKind::Index(ref base, ref idx) => self.check_expr_index(base, idx, needs, expr),
+            ExprKind::Index(ref base, ref idx) => {
+                self.check_expr_index(base, idx, expected, needs, expr)
+            }
             ExprKind::Yield(ref value, ref src) => self.check_expr_yield(value, expr, src),
             hir::ExprKind::Err => tcx.types.err,
         }
@@ -884,14 +888,18 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         };
 
         // Call the generic checker.
-        self.check_method_argument_types(
+        let ty = self.check_method_argument_types(
             span,
             expr,
             method,
             &args[1..],
             DontTupleArguments,
             expected,
-        )
+        );
+        if let Ok(method) = method {
+            self.check_binary_op_method_call(expr, args, method.def_id);
+        }
+        ty
     }
 
     fn report_extended_method_error(
@@ -1720,6 +1728,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         &self,
         base: &'tcx hir::Expr<'tcx>,
         idx: &'tcx hir::Expr<'tcx>,
+        expected: Expectation<'tcx>,
         needs: Needs,
         expr: &'tcx hir::Expr<'tcx>,
     ) -> Ty<'tcx> {
@@ -1732,51 +1741,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             idx_t
         } else {
             let base_t = self.structurally_resolved_type(base.span, base_t);
-            match self.lookup_indexing(expr, base, base_t, idx_t, needs) {
-                Some((index_ty, element_ty)) => {
-                    // two-phase not needed because index_ty is never mutable
-                    self.demand_coerce(idx, idx_t, index_ty, AllowTwoPhase::No);
-                    element_ty
-                }
-                None => {
-                    let mut err = type_error_struct!(
-                        self.tcx.sess,
-                        expr.span,
-                        base_t,
-                        E0608,
-                        "cannot index into a value of type `{}`",
-                        base_t
-                    );
-                    // Try to give some advice about indexing tuples.
-                    if let ty::Tuple(..) = base_t.kind {
-                        let mut needs_note = true;
-                        // If the index is an integer, we can show the actual
-                        // fixed expression:
-                        if let ExprKind::Lit(ref lit) = idx.kind {
-                            if let ast::LitKind::Int(i, ast::LitIntType::Unsuffixed) = lit.node {
-                                let snip = self.tcx.sess.source_map().span_to_snippet(base.span);
-                                if let Ok(snip) = snip {
-                                    err.span_suggestion(
-                                        expr.span,
-                                        "to access tuple elements, use",
-                                        format!("{}.{}", snip, i),
-                                        Applicability::MachineApplicable,
-                                    );
-                                    needs_note = false;
-                                }
-                            }
-                        }
-                        if needs_note {
-                            err.help(
-                                "to access tuple elements, use tuple indexing \
-                                        syntax (e.g., `tuple.0`)",
-                            );
-                        }
-                    }
-                    err.emit();
-                    self.tcx.types.err
-                }
-            }
+            self.check_index_op(expr, base, idx, base_t, idx_t, expected, needs)
         }
     }
 
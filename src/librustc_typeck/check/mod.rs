@@ -96,7 +96,9 @@ use rustc_attr as attr;
 use rustc_data_structures::captures::Captures;
 use rustc_data_structures::fx::{FxHashMap, FxHashSet};
 use rustc_errors::ErrorReported;
-use rustc_errors::{pluralize, struct_span_err, Applicability, DiagnosticBuilder, DiagnosticId};
+use rustc_errors::{
+    pluralize, struct_span_err, Applicability, Diagnostic, DiagnosticBuilder, DiagnosticId,
+};
 use rustc_hir as hir;
 use rustc_hir::def::{CtorOf, DefKind, Res};
 use rustc_hir::def_id::{CrateNum, DefId, DefIdMap, DefIdSet, LocalDefId, LOCAL_CRATE};
@@ -134,7 +136,7 @@ use rustc_session::parse::feature_err;
 use rustc_session::Session;
 use rustc_span::hygiene::DesugaringKind;
 use rustc_span::source_map::{original_sp, DUMMY_SP};
-use rustc_span::symbol::{kw, sym, Ident};
+use rustc_span::symbol::{kw, sym, Ident, Symbol};
 use rustc_span::{self, BytePos, MultiSpan, Span};
 use rustc_target::abi::VariantIdx;
 use rustc_target::spec::abi::Abi;
@@ -241,6 +243,14 @@ pub struct Inherited<'a, 'tcx> {
 
     deferred_generator_interiors: RefCell<Vec<(hir::BodyId, Ty<'tcx>, hir::GeneratorKind)>>,
 
+    // Operator errors that can be fixed by restricting a type parameter. They are reported at
+    // the end of typeck so that a single suggestion can add all the missing bounds.
+    deferred_op_bound_errors: RefCell<Vec<(Diagnostic, ty::ParamTy, String)>>,
+
+    // Shifts by a literal amount, along with the type of the shifted value. They are checked for
+    // overflow after fallback, when the width of integer literals is known.
+    deferred_shift_checks: RefCell<Vec<(&'tcx hir::Expr<'tcx>, Ty<'tcx>)>>,
+
     // Opaque types found in explicit return types and their
     // associated fresh inference variable. Writeback resolves these
     // variables to get the concrete type, which can be used to
@@ -678,6 +688,8 @@ impl Inherited<'a, 'tcx> {
             deferred_call_resolutions: RefCell::new(Default::default()),
             deferred_cast_checks: RefCell::new(Vec::new()),
             deferred_generator_interiors: RefCell::new(Vec::new()),
+            deferred_op_bound_errors: RefCell::new(Vec::new()),
+            deferred_shift_checks: RefCell::new(Vec::new()),
             opaque_types: RefCell::new(Default::default()),
             opaque_types_vars: RefCell::new(Default::default()),
             implicit_region_bound: None,
@@ -1056,6 +1068,10 @@ fn typeck_tables_of_with_fallback<'tcx>(
             fcx
         };
 
+        // Operator errors fixable by bounds on type params are only emitted once all of them are
+        // known, so their suggestions can be combined.
+        fcx.report_deferred_op_bound_errors();
+
         // All type checking constraints were added, try to fallback unsolved variables.
         fcx.select_obligations_where_possible(false, |_| {});
         let mut fallback_has_occurred = false;
@@ -1104,6 +1120,7 @@ fn typeck_tables_of_with_fallback<'tcx>(
         // Even though coercion casts provide type hints, we check casts after fallback for
         // backwards compatibility. This makes fallback a stronger type hint than a cast coercion.
         fcx.check_casts();
+        fcx.check_deferred_shift_overflows();
 
         // Closure and generator analysis may run after fallback
         // because they don't constrain other type variables.
@@ -2155,7 +2172,14 @@ fn check_impl_items_against_trait<'tcx>(
     }
 
     if !missing_items.is_empty() {
-        missing_items_err(tcx, impl_span, &missing_items, full_impl_span);
+        missing_items_err(
+            tcx,
+            impl_span,
+            &missing_items,
+            full_impl_span,
+            impl_trait_ref.def_id,
+            impl_item_refs,
+        );
     }
 }
 
@@ -2164,6 +2188,8 @@ fn missing_items_err(
     impl_span: Span,
     missing_items: &[ty::AssocItem],
     full_impl_span: Span,
+    trait_def_id: DefId,
+    impl_item_refs: &[hir::ImplItemRef<'_>],
 ) {
     let missing_items_msg = missing_items
         .iter()
@@ -2191,6 +2217,12 @@ fn missing_items_err(
     let padding: String = (0..indentation).map(|_| " ").collect();
 
     for trait_item in missing_items {
+        if trait_item.kind == ty::AssocKind::Type
+            && trait_item.ident.name == sym::Output
+            && suggest_operator_output(tcx, &mut err, trait_def_id, impl_item_refs)
+        {
+            continue;
+        }
         let snippet = suggestion_signature(&trait_item, tcx);
         let code = format!("{}{}\n{}", padding, snippet, padding);
         let msg = format!("implement the missing item: `{}`", snippet);
@@ -2205,6 +2237,78 @@ fn missing_items_err(
     err.emit();
 }
 
+/// Explains that the `Output` of an operator trait is the return type of its method, and suggests
+/// setting it to the return type written on the method in the `impl`. Returns `false` if the trait
+/// isn't an operator trait or there is no such return type to suggest.
+fn suggest_operator_output(
+    tcx: TyCtxt<'_>,
+    err: &mut DiagnosticBuilder<'_>,
+    trait_def_id: DefId,
+    impl_item_refs: &[hir::ImplItemRef<'_>],
+) -> bool {
+    let lang_items = tcx.lang_items();
+    let operator_traits = [
+        lang_items.add_trait(),
+        lang_items.sub_trait(),
+        lang_items.mul_trait(),
+        lang_items.div_trait(),
+        lang_items.rem_trait(),
+        lang_items.bitxor_trait(),
+        lang_items.bitand_trait(),
+        lang_items.bitor_trait(),
+        lang_items.shl_trait(),
+        lang_items.shr_trait(),
+        lang_items.neg_trait(),
+        lang_items.not_trait(),
+    ];
+    if !operator_traits.contains(&Some(trait_def_id)) {
+        return false;
+    }
+    let method_and_output = impl_item_refs.iter().find_map(|impl_item_ref| {
+        let impl_item = tcx.hir().impl_item(impl_item_ref.id);
+        match impl_item.kind {
+            hir::ImplItemKind::Fn(ref sig, _) => match sig.decl.output {
+                hir::FnRetTy::Return(ty) => Some((impl_item, ty)),
+                hir::FnRetTy::DefaultReturn(_) => None,
+            },
+            _ => None,
+        }
+    });
+    let (method, output) = match method_and_output {
+        Some(method_and_output) => method_and_output,
+        None => return false,
+    };
+    // There is nothing to suggest if the method returns `Self::Output` itself.
+    let returns_output = match output.kind {
+        hir::TyKind::Path(hir::QPath::TypeRelative(_, segment)) => {
+            segment.ident.name == sym::Output
+        }
+        hir::TyKind::Path(hir::QPath::Resolved(Some(_), path)) => {
+            path.segments.last().map_or(false, |segment| segment.ident.name == sym::Output)
+        }
+        _ => false,
+    };
+    let source_map = tcx.sess.source_map();
+    let output = match source_map.span_to_snippet(output.span) {
+        Ok(output) if !returns_output => output,
+        _ => return false,
+    };
+    err.note(&format!(
+        "`Output` is the type `{}` returns, and the type the operator evaluates to, so it should \
+         be `{}` here",
+        method.ident, output
+    ));
+    let indentation = source_map.span_to_margin(method.span).unwrap_or(0);
+    let padding: String = (0..indentation).map(|_| " ").collect();
+    err.span_suggestion_verbose(
+        method.span.shrink_to_lo(),
+        "add the `Output` associated type",
+        format!("type Output = {};\n{}", output, padding),
+        Applicability::MaybeIncorrect,
+    );
+    true
+}
+
 /// Resugar `ty::GenericPredicates` in a way suitable to be used in structured suggestions.
 fn bounds_from_generic_predicates(
     tcx: TyCtxt<'_>,
@@ -2591,25 +2695,56 @@ fn check_transparent<'tcx>(tcx: TyCtxt<'tcx>, sp: Span, adt: &'tcx ty::AdtDef) {
         }
     }
 
-    // For each field, figure out if it's known to be a ZST and align(1)
-    let field_infos = adt.all_fields().map(|field| {
+    // If all the generic parameters have defaults, the type is also checked the way it looks
+    // when instantiated with them, which is how users will most likely encounter it.
+    let default_substs = default_substs_for_item(tcx, adt.did);
+
+    // For each field, figure out if it's known to be a ZST and align(1), and which
+    // `#[non_exhaustive]` types from other crates it contains if it's a ZST
+    let field_infos = adt.all_fields().map(move |field| {
         let ty = field.ty(tcx, InternalSubsts::identity_for_item(tcx, field.did));
         let param_env = tcx.param_env(field.did);
         let layout = tcx.layout_of(param_env.and(ty));
         // We are currently checking the type this field came from, so it must be local
-        let span = tcx.hir().span_if_local(field.did).unwrap();
+        let hir_id = tcx.hir().as_local_hir_id(field.did.expect_local());
+        let span = tcx.hir().span(hir_id);
         let zst = layout.map(|layout| layout.is_zst()).unwrap_or(false);
         let align1 = layout.map(|layout| layout.align.abi.bytes() == 1).unwrap_or(false);
-        (span, zst, align1)
+        let mut non_exhaustive = vec![];
+        if zst {
+            external_non_exhaustive_types(tcx, ty, &mut non_exhaustive);
+        }
+        // The type of the field with the defaults applied, if only that one is problematic.
+        let mut defaulted_ty = None;
+        if let Some(substs) = default_substs.filter(|_| non_exhaustive.is_empty()) {
+            let default_ty = tcx.erase_regions(&field.ty(tcx, substs));
+            if default_ty != tcx.erase_regions(&ty) {
+                let default_zst = tcx
+                    .layout_of(param_env.and(default_ty))
+                    .map(|layout| layout.is_zst())
+                    .unwrap_or(false);
+                if default_zst {
+                    external_non_exhaustive_types(tcx, default_ty, &mut non_exhaustive);
+                    if !non_exhaustive.is_empty() {
+                        defaulted_ty = Some(default_ty);
+                    }
+                }
+            }
+        }
+        (span, hir_id, zst, align1, non_exhaustive, defaulted_ty)
     });
 
-    let non_zst_fields =
-        field_infos.clone().filter_map(|(span, zst, _align1)| if !zst { Some(span) } else { None });
+    let non_zst_fields = field_infos.clone().filter_map(
+        |(span, _hir_id, zst, _align1, _non_exhaustive, _defaulted_ty)| {
+            if !zst { Some(span) } else { None }
+        },
+    );
     let non_zst_count = non_zst_fields.clone().count();
     if non_zst_count != 1 {
         bad_non_zero_sized_fields(tcx, adt, non_zst_count, non_zst_fields, sp);
     }
-    for (span, zst, align1) in field_infos {
+    let mut non_exhaustive_fields = vec![];
+    for (span, hir_id, zst, align1, non_exhaustive, defaulted_ty) in field_infos {
         if zst && !align1 {
             struct_span_err!(
                 tcx.sess,
@@ -2621,6 +2756,228 @@ fn check_transparent<'tcx>(tcx: TyCtxt<'tcx>, sp: Span, adt: &'tcx ty::AdtDef) {
             .span_label(span, "has alignment larger than 1")
             .emit();
         }
+        if non_exhaustive.is_empty() {
+            continue;
+        }
+        // Fields on which the lint is allowed are left out of the lint emitted for the item.
+        let (level, _) =
+            tcx.lint_level_at_node(lint::builtin::REPR_TRANSPARENT_EXTERNAL_NON_EXHAUSTIVE, hir_id);
+        if level == lint::Level::Allow {
+            continue;
+        }
+        let field_hir_ty = match tcx.hir().get(hir_id) {
+            Node::Field(field) => Some(field.ty),
+            _ => None,
+        };
+        non_exhaustive_fields.push((span, non_exhaustive, defaulted_ty, field_hir_ty));
+    }
+    if non_zst_count == 1 {
+        repr_transparent_size_check(tcx, sp, adt);
+    }
+    if non_exhaustive_fields.is_empty() {
+        return;
+    }
+
+    // All the findings are reported in a single diagnostic, which is emitted on the item.
+    let item_hir_id = tcx.hir().as_local_hir_id(adt.did.expect_local());
+    let field_spans: Vec<_> = non_exhaustive_fields.iter().map(|(span, ..)| *span).collect();
+    tcx.struct_span_lint_hir(
+        lint::builtin::REPR_TRANSPARENT_EXTERNAL_NON_EXHAUSTIVE,
+        item_hir_id,
+        MultiSpan::from_spans(field_spans),
+        |lint| {
+            let mut err = match &non_exhaustive_fields[..] {
+                [(_, tys, ..)] if tys.len() == 1 => lint.build(&format!(
+                    "zero-sized field in transparent {} can't contain external non exhaustive \
+                     type `{}`",
+                    adt.descr(),
+                    tys[0],
+                )),
+                [_] => lint.build(&format!(
+                    "zero-sized field in transparent {} can't contain external non exhaustive \
+                     types",
+                    adt.descr(),
+                )),
+                _ => lint.build(&format!(
+                    "zero-sized fields in transparent {} can't contain external non exhaustive \
+                     types",
+                    adt.descr(),
+                )),
+            };
+            let several = non_exhaustive_fields.len() > 1 || non_exhaustive_fields[0].1.len() > 1;
+            for (span, tys, defaulted_ty, field_hir_ty) in &non_exhaustive_fields {
+                for ty in tys {
+                    // Point at the type within the field's type, if it is written out there and
+                    // isn't the whole field type. Otherwise, label the field itself if the
+                    // message doesn't already say which type this is about.
+                    let mut type_spans = vec![];
+                    if let (Some(hir_ty), ty::Adt(def, _)) = (*field_hir_ty, &ty.kind) {
+                        let mut finder = AdtPathFinder { did: def.did, spans: vec![] };
+                        finder.visit_ty(hir_ty);
+                        type_spans = finder.spans;
+                        type_spans.retain(|type_span| *type_span != hir_ty.span);
+                    }
+                    for type_span in &type_spans {
+                        err.span_label(*type_span, "this type is `#[non_exhaustive]`");
+                    }
+                    if type_spans.is_empty() && several {
+                        err.span_label(*span, format!("contains `{}`", ty));
+                    }
+                }
+                if let Some(default_ty) = defaulted_ty {
+                    err.note(&format!(
+                        "this field has type `{}` when the default type parameters of `{}` are \
+                         used",
+                        default_ty,
+                        tcx.def_path_str(adt.did),
+                    ));
+                }
+            }
+            err.note(
+                "`#[non_exhaustive]` types from other crates may become non-zero-sized in the \
+                 future",
+            )
+            .emit();
+        },
+    );
+}
+
+/// Checks that a transparent type has the same size as its non-zero-sized field. This holds by
+/// construction, so a mismatch means that the layout of one of them was computed incorrectly.
+fn repr_transparent_size_check<'tcx>(tcx: TyCtxt<'tcx>, sp: Span, adt: &'tcx ty::AdtDef) {
+    let param_env = tcx.param_env(adt.did);
+    let substs = InternalSubsts::identity_for_item(tcx, adt.did);
+    let mut non_zst_fields = adt
+        .all_fields()
+        .map(|field| field.ty(tcx, substs))
+        .filter(|&ty| !tcx.layout_of(param_env.and(ty)).map_or(false, |layout| layout.is_zst()));
+    let field_ty = match (non_zst_fields.next(), non_zst_fields.next()) {
+        (Some(field_ty), None) => field_ty,
+        _ => return,
+    };
+    let adt_ty = tcx.mk_adt(adt, substs);
+    // The size of generic types may not be known.
+    let (adt_size, field_size) =
+        match (tcx.layout_of(param_env.and(adt_ty)), tcx.layout_of(param_env.and(field_ty))) {
+            (Ok(adt_layout), Ok(field_layout)) => (adt_layout.size, field_layout.size),
+            _ => return,
+        };
+    if adt_size != field_size {
+        tcx.sess.delay_span_bug(
+            sp,
+            &format!(
+                "transparent {} `{}` has size {} but its non-zero-sized field of type `{}` has \
+                 size {}",
+                adt.descr(),
+                adt_ty,
+                adt_size.bytes(),
+                field_ty,
+                field_size.bytes(),
+            ),
+        );
+    }
+}
+
+/// Collects the spans of the paths to the ADT `did` within a HIR type.
+struct AdtPathFinder {
+    did: DefId,
+    spans: Vec<Span>,
+}
+
+impl<'tcx> Visitor<'tcx> for AdtPathFinder {
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_ty(&mut self, ty: &'tcx hir::Ty<'tcx>) {
+        if let hir::TyKind::Path(QPath::Resolved(None, path)) = &ty.kind {
+            if let Res::Def(DefKind::Struct | DefKind::Enum | DefKind::Union, did) = path.res {
+                if did == self.did {
+                    self.spans.push(ty.span);
+                }
+            }
+        }
+        intravisit::walk_ty(self, ty);
+    }
+}
+
+/// Returns the substitutions that instantiate the item with the defaults of its type parameters,
+/// or `None` if it has no type parameters, or some of them have no default.
+fn default_substs_for_item<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId) -> Option<SubstsRef<'tcx>> {
+    let generics = tcx.generics_of(def_id);
+    let mut has_type_params = false;
+    for param in &generics.params {
+        match param.kind {
+            GenericParamDefKind::Lifetime => {}
+            GenericParamDefKind::Type { has_default: true, .. } => has_type_params = true,
+            GenericParamDefKind::Type { has_default: false, .. } | GenericParamDefKind::Const => {
+                return None;
+            }
+        }
+    }
+    if !has_type_params || generics.parent.is_some() {
+        return None;
+    }
+    Some(InternalSubsts::for_item(tcx, def_id, |param, substs| match param.kind {
+        GenericParamDefKind::Lifetime => tcx.lifetimes.re_erased.into(),
+        // Defaults may refer to the parameters before them, e.g. `struct S<A = u8, B = A>`.
+        GenericParamDefKind::Type { .. } => tcx.type_of(param.def_id).subst(tcx, substs).into(),
+        GenericParamDefKind::Const => bug!("const parameters can't have defaults"),
+    }))
+}
+
+/// Foreign types that are guaranteed to stay zero-sized forever, in addition to `PhantomData`.
+/// Fields containing them are fine in transparent types even if they were to become
+/// `#[non_exhaustive]`, so they are not looked into.
+const PERMANENT_ZST_DIAGNOSTIC_ITEMS: &[Symbol] = &[sym::PhantomPinned];
+
+/// Whether `ty` is guaranteed to be zero-sized forever, no matter what its constituent types are.
+fn is_permanent_zst<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    match ty.kind {
+        ty::Adt(def, _) => {
+            def.is_phantom_data()
+                || PERMANENT_ZST_DIAGNOSTIC_ITEMS
+                    .iter()
+                    .any(|&name| tcx.is_diagnostic_item(name, def.did))
+        }
+        // A zero-length array of a local type can't be affected by changes in other crates.
+        ty::Array(elem_ty, len) => {
+            len.try_eval_usize(tcx, ty::ParamEnv::reveal_all()) == Some(0)
+                && matches!(elem_ty.kind, ty::Adt(def, _) if def.did.is_local())
+        }
+        _ => false,
+    }
+}
+
+/// Collects the `#[non_exhaustive]` types from other crates contained in the zero-sized type `ty`,
+/// which are allowed to grow non-zero-sized fields without this being a breaking change.
+fn external_non_exhaustive_types<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>, found: &mut Vec<Ty<'tcx>>) {
+    if is_permanent_zst(tcx, ty) {
+        return;
+    }
+    match ty.kind {
+        ty::Tuple(_) => {
+            for ty in ty.tuple_fields() {
+                external_non_exhaustive_types(tcx, ty, found);
+            }
+        }
+        ty::Array(ty, _) => external_non_exhaustive_types(tcx, ty, found),
+        ty::Adt(def, substs) => {
+            let non_exhaustive = def.is_variant_list_non_exhaustive()
+                || def.variants.iter().any(|variant| variant.is_field_list_non_exhaustive());
+            if non_exhaustive && !def.did.is_local() {
+                if !found.contains(&ty) {
+                    found.push(ty);
+                }
+            } else {
+                for field in def.all_fields() {
+                    external_non_exhaustive_types(tcx, field.ty(tcx, substs), found);
+                }
+            }
+        }
+        _ => {}
     }
 }
 
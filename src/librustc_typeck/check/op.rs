@@ -1,18 +1,42 @@
-//! Code related to processing overloaded binary and unary operators.
+//! Code related to processing overloaded binary, unary and index operators.
 
 use super::method::MethodCallee;
-use super::{FnCtxt, Needs};
-use rustc_errors::{self, struct_span_err, Applicability, DiagnosticBuilder};
+use super::{Expectation, FnCtxt, Needs, NoExpectation};
+use crate::type_error_struct;
+use rustc_ast::ast;
+use rustc_ast::util::parser::{AssocOp, PREC_POSTFIX, PREC_PREFIX};
+use rustc_errors::{
+    self, pluralize, struct_span_err, Applicability, Diagnostic, DiagnosticBuilder,
+};
 use rustc_hir as hir;
+use rustc_hir::def::{CtorKind, DefKind, Res};
+use rustc_hir::def_id::{DefId, LOCAL_CRATE};
+use rustc_hir::intravisit::{self, NestedVisitorMap, Visitor};
 use rustc_infer::infer::type_variable::{TypeVariableOrigin, TypeVariableOriginKind};
+use rustc_infer::infer::RegionVariableOrigin;
+use rustc_middle::hir::map::blocks::FnLikeNode;
 use rustc_middle::ty::adjustment::{
     Adjust, Adjustment, AllowTwoPhase, AutoBorrow, AutoBorrowMutability,
 };
+use rustc_middle::ty::subst::GenericArg;
 use rustc_middle::ty::TyKind::{Adt, Array, Char, FnDef, Never, Ref, Str, Tuple, Uint};
-use rustc_middle::ty::{self, suggest_constraining_type_param, Ty, TyCtxt, TypeFoldable};
-use rustc_span::symbol::Ident;
+use rustc_middle::ty::{
+    self, suggest_constraining_type_params, DefIdTree, ToPolyTraitRef, ToPredicate, Ty, TyCtxt,
+    TypeFoldable, WithConstness,
+};
+use rustc_session::lint;
+use rustc_span::hygiene::{ExpnKind, MacroKind};
+use rustc_span::symbol::{sym, Ident};
 use rustc_span::Span;
 use rustc_trait_selection::infer::InferCtxtExt;
+use rustc_trait_selection::traits;
+use rustc_trait_selection::traits::query::evaluate_obligation::InferCtxtExt as _;
+
+use std::collections::hash_map::DefaultHasher;
+use std::convert::TryFrom;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::path::PathBuf;
 
 impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
     /// Checks a `a <op>= b`
@@ -24,21 +48,56 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         rhs: &'tcx hir::Expr<'tcx>,
     ) -> Ty<'tcx> {
         let (lhs_ty, rhs_ty, return_ty) =
-            self.check_overloaded_binop(expr, lhs, rhs, op, IsAssign::Yes);
+            self.check_overloaded_binop(expr, lhs, rhs, op, IsAssign::Yes, NoExpectation);
+        self.defer_shift_overflow_check(expr, rhs, lhs_ty, op);
+        self.check_shift_by_negative(expr, rhs, op);
 
-        let ty =
-            if !lhs_ty.is_ty_var() && !rhs_ty.is_ty_var() && is_builtin_binop(lhs_ty, rhs_ty, op) {
-                self.enforce_builtin_binop_types(&lhs.span, lhs_ty, &rhs.span, rhs_ty, op);
-                self.tcx.mk_unit()
-            } else {
-                return_ty
-            };
+        let ty = if !lhs_ty.is_ty_var()
+            && !rhs_ty.is_ty_var()
+            && is_builtin_binop(self.tcx, lhs_ty, rhs_ty, op)
+        {
+            self.enforce_builtin_binop_types(
+                &lhs.span,
+                lhs_ty,
+                &rhs.span,
+                rhs_ty,
+                op,
+                IsAssign::Yes,
+            );
+            self.check_potential_underflow(expr, lhs, rhs, lhs_ty, op);
+            self.tcx.mk_unit()
+        } else {
+            return_ty
+        };
 
-        self.check_lhs_assignable(lhs, "E0067", &op.span);
+        if !self.report_op_assign_to_const(lhs, op) {
+            self.check_lhs_assignable(lhs, "E0067", &op.span);
+        }
 
         ty
     }
 
+    /// Reports a compound assignment to a `const` item, which `check_lhs_assignable` would only
+    /// call an invalid left-hand side. Returns whether an error was reported.
+    fn report_op_assign_to_const(&self, lhs: &'tcx hir::Expr<'tcx>, op: hir::BinOp) -> bool {
+        let res = match lhs.kind {
+            hir::ExprKind::Path(ref qpath) => self.tables.borrow().qpath_res(qpath, lhs.hir_id),
+            _ => return false,
+        };
+        if !matches!(res, Res::Def(DefKind::Const, _) | Res::Def(DefKind::AssocConst, _)) {
+            return false;
+        }
+        let label = match self.tcx.sess.source_map().span_to_snippet(lhs.span) {
+            Ok(name) => format!("`{}` is a constant", name),
+            Err(_) => "this is a constant".to_string(),
+        };
+        struct_span_err!(self.tcx.sess, op.span, E0067, "constants cannot be mutated")
+            .span_label(lhs.span, label)
+            .help("consider using a `static mut` or `let mut` instead")
+            .emit();
+        true
+    }
+
     /// Checks a potentially overloaded binary operator.
     pub fn check_binop(
         &self,
@@ -46,6 +105,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         op: hir::BinOp,
         lhs_expr: &'tcx hir::Expr<'tcx>,
         rhs_expr: &'tcx hir::Expr<'tcx>,
+        expected: Expectation<'tcx>,
     ) -> Ty<'tcx> {
         let tcx = self.tcx;
 
@@ -57,9 +117,13 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         match BinOpCategory::from(op) {
             BinOpCategory::Shortcircuit => {
                 // && and || are a simple case.
-                self.check_expr_coercable_to_type(lhs_expr, tcx.types.bool);
+                self.check_short_circuit_operand(expr, lhs_expr);
                 let lhs_diverges = self.diverges.get();
-                self.check_expr_coercable_to_type(rhs_expr, tcx.types.bool);
+                self.check_short_circuit_operand(expr, rhs_expr);
+                if !lhs_diverges.is_always() && self.diverges.get().is_always() {
+                    self.check_diverging_short_circuit_rhs(expr, lhs_expr, rhs_expr, op);
+                }
+                self.check_equality_chain(expr, op);
 
                 // Depending on the LHS' value, the RHS can never execute.
                 self.diverges.set(lhs_diverges);
@@ -70,8 +134,17 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 // Otherwise, we always treat operators as if they are
                 // overloaded. This is the way to be most flexible w/r/t
                 // types that get inferred.
-                let (lhs_ty, rhs_ty, return_ty) =
-                    self.check_overloaded_binop(expr, lhs_expr, rhs_expr, op, IsAssign::No);
+                let (lhs_ty, rhs_ty, return_ty) = self.check_overloaded_binop(
+                    expr,
+                    lhs_expr,
+                    rhs_expr,
+                    op,
+                    IsAssign::No,
+                    expected,
+                );
+                self.defer_shift_overflow_check(expr, rhs_expr, lhs_ty, op);
+                self.check_shift_by_negative(expr, rhs_expr, op);
+                self.check_op_associativity_hint(expr, lhs_expr, rhs_expr, op);
 
                 // Supply type inference hints if relevant. Probably these
                 // hints should be enforced during select as part of the
@@ -87,7 +160,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 // can't pin this down to a specific impl.
                 if !lhs_ty.is_ty_var()
                     && !rhs_ty.is_ty_var()
-                    && is_builtin_binop(lhs_ty, rhs_ty, op)
+                    && is_builtin_binop(self.tcx, lhs_ty, rhs_ty, op)
                 {
                     let builtin_return_ty = self.enforce_builtin_binop_types(
                         &lhs_expr.span,
@@ -95,8 +168,15 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                         &rhs_expr.span,
                         rhs_ty,
                         op,
+                        IsAssign::No,
                     );
                     self.demand_suptype(expr.span, builtin_return_ty, return_ty);
+                    self.check_fn_ptr_comparison(expr, lhs_ty, rhs_ty, op);
+                    self.check_xor_used_as_pow(expr, lhs_expr, rhs_expr, lhs_ty, op);
+                    self.check_self_comparison(expr, lhs_expr, rhs_expr, lhs_ty, op);
+                    self.check_non_short_circuit_bool_op(expr, rhs_expr, lhs_ty, rhs_ty, op);
+                    self.check_identity_op(expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+                    self.check_negated_equality_operand(expr, lhs_expr, rhs_expr, lhs_ty, op);
                 }
 
                 return_ty
@@ -111,8 +191,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         rhs_span: &Span,
         rhs_ty: Ty<'tcx>,
         op: hir::BinOp,
+        is_assign: IsAssign,
     ) -> Ty<'tcx> {
-        debug_assert!(is_builtin_binop(lhs_ty, rhs_ty, op));
+        debug_assert!(is_builtin_binop(self.tcx, lhs_ty, rhs_ty, op));
 
         // Special-case a single layer of referencing, so that things like `5.0 + &6.0f32` work.
         // (See https://github.com/rust-lang/rust/issues/57447.)
@@ -127,24 +208,52 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             }
 
             BinOpCategory::Shift => {
-                // result type is same as LHS always
+                // result type is same as LHS always, also when shifting every lane of a SIMD
+                // vector by the same scalar amount
                 lhs_ty
             }
 
             BinOpCategory::Math | BinOpCategory::Bitwise => {
                 // both LHS and RHS and result will have the same type
-                self.demand_suptype(*rhs_span, lhs_ty, rhs_ty);
+                self.demand_same_operand_types(*lhs_span, lhs_ty, *rhs_span, rhs_ty, op, is_assign);
                 lhs_ty
             }
 
             BinOpCategory::Comparison => {
                 // both LHS and RHS and result will have the same type
-                self.demand_suptype(*rhs_span, lhs_ty, rhs_ty);
+                self.demand_same_operand_types(*lhs_span, lhs_ty, *rhs_span, rhs_ty, op, is_assign);
                 tcx.types.bool
             }
         }
     }
 
+    /// Requires the RHS of a builtin operation to have the type of the LHS. A mismatch is
+    /// reported on the RHS, with labels on the LHS and the operator explaining where the expected
+    /// type comes from.
+    fn demand_same_operand_types(
+        &self,
+        lhs_span: Span,
+        lhs_ty: Ty<'tcx>,
+        rhs_span: Span,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        if let Some(mut err) = self.demand_suptype_diag(rhs_span, lhs_ty, rhs_ty) {
+            let lhs_ty = self.resolve_vars_if_possible(&lhs_ty);
+            err.span_label(lhs_span, format!("expected because this is `{}`", lhs_ty));
+            err.span_label(
+                op.span,
+                format!(
+                    "`{}{}` requires both operands to have the same type",
+                    op.node.as_str(),
+                    if is_assign == IsAssign::Yes { "=" } else { "" },
+                ),
+            );
+            err.emit();
+        }
+    }
+
     fn check_overloaded_binop(
         &self,
         expr: &'tcx hir::Expr<'tcx>,
@@ -152,6 +261,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         rhs_expr: &'tcx hir::Expr<'tcx>,
         op: hir::BinOp,
         is_assign: IsAssign,
+        expected: Expectation<'tcx>,
     ) -> (Ty<'tcx>, Ty<'tcx>, Ty<'tcx>) {
         debug!(
             "check_overloaded_binop(expr.hir_id={}, op={:?}, is_assign={:?})",
@@ -193,10 +303,63 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             span: rhs_expr.span,
         });
 
-        let result = self.lookup_op_method(lhs_ty, &[rhs_ty_var], Op::Binary(op, is_assign));
+        let mut result = self.lookup_op_method(lhs_ty, &[rhs_ty_var], Op::Binary(op, is_assign));
+
+        // If the type of the whole expression is expected to be some `C`, the `Output = C` of the
+        // operator trait may tell us more about the RHS than `rhs_ty_var` does, e.g., when the
+        // RHS is a closure whose signature has to be deduced from its expected type.
+        let rhs_hint = match result {
+            Ok(ref method) => self
+                .expected_inputs_for_expected_output(
+                    expr.span,
+                    expected,
+                    method.sig.output(),
+                    &[rhs_ty_var],
+                )
+                .get(0)
+                .copied()
+                .filter(|hint| !hint.is_ty_var()),
+            Err(()) => None,
+        };
 
         // see `NB` above
-        let rhs_ty = self.check_expr_coercable_to_type(rhs_expr, rhs_ty_var);
+        let rhs_ty = self.check_expr_with_hint(rhs_expr, rhs_hint.unwrap_or(rhs_ty_var));
+        let rhs_ty = if result.is_ok()
+            && self.report_simd_operand_mismatch(rhs_expr, lhs_ty, rhs_ty, rhs_ty_var, op)
+        {
+            // Don't report the mismatch a second time as a failed coercion.
+            self.demand_suptype(rhs_expr.span, rhs_ty_var, self.tcx.types.err);
+            self.tcx.types.err
+        } else if result.is_ok()
+            // Cheaply rule out the common case of an RHS whose type is known before evaluating
+            // any predicates.
+            && self.resolve_vars_with_obligations(rhs_ty_var).is_ty_var()
+            && !self.op_may_apply(lhs_ty, &[rhs_ty], Op::Binary(op, is_assign))
+            && (self.is_op_only_implemented_for_shared_rhs(lhs_ty, rhs_ty, op, is_assign)
+                || self.is_op_implemented_for_rhs_lock_contents(lhs_ty, rhs_ty, op, is_assign)
+                || self.is_op_implemented_for_rhs_cell_contents(lhs_ty, rhs_ty, op, is_assign))
+        {
+            // The operator is going to fail for this RHS. With a single implementation, the RHS
+            // would have been coerced to its shared reference type. Otherwise, report the missing
+            // implementation below, where borrowing the RHS immutably, locking it or reading it
+            // out of its `Cell` is suggested, rather than as an unfulfilled obligation. None of
+            // these checks registers obligations, so they don't affect inference.
+            self.demand_suptype(rhs_expr.span, rhs_ty_var, self.tcx.types.err);
+            result = Err(());
+            rhs_ty
+        } else {
+            let (coerced_ty, err) =
+                self.demand_coerce_diag(rhs_expr, rhs_ty, rhs_ty_var, AllowTwoPhase::No);
+            if let Some(mut err) = err {
+                self.suggest_unwrapping_reverse_operand(
+                    &mut err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op,
+                );
+                self.check_op_on_option_bool(&mut err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+                self.check_cmp_with_bool_and_integer(&mut err, lhs_expr, rhs_expr, lhs_ty, op);
+                err.emit();
+            }
+            coerced_ty
+        };
         let rhs_ty = self.resolve_vars_with_obligations(rhs_ty);
 
         let return_ty = match result {
@@ -246,181 +409,134 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     }
                 }
                 self.write_method_call(expr.hir_id, method);
+                self.check_op_on_zst(expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
 
                 method.sig.output()
             }
+            // With `#![feature(simd_shift_by_scalar)]`, shifting every lane of an integer vector
+            // by a scalar amount is builtin, without an operator impl to find.
+            Err(()) if lhs_ty.is_simd() && is_builtin_binop(self.tcx, lhs_ty, rhs_ty, op) => lhs_ty,
             Err(()) => {
                 // error types are considered "builtin"
                 if !lhs_ty.references_error() && !rhs_ty.references_error() {
-                    let source_map = self.tcx.sess.source_map();
+                    // Errors on an operation written by a macro point at the call of the macro,
+                    // and only at those operands that are arguments of the call.
+                    let macro_call = macro_call_site(expr.span);
+                    let is_visible = |span: Span| macro_call.is_none() || !span.from_expansion();
+                    let mut long_ty_paths = vec![];
+                    let lhs_ty_str = self.operand_ty_string(lhs_ty, &mut long_ty_paths);
+                    let rhs_ty_str = self.operand_ty_string(rhs_ty, &mut long_ty_paths);
 
                     match is_assign {
                         IsAssign::Yes => {
                             let mut err = struct_span_err!(
                                 self.tcx.sess,
-                                expr.span,
+                                macro_call.unwrap_or(expr.span),
                                 E0368,
                                 "binary assignment operation `{}=` cannot be applied to type `{}`",
                                 op.node.as_str(),
-                                lhs_ty,
-                            );
-                            err.span_label(
-                                lhs_expr.span,
-                                format!("cannot use `{}=` on type `{}`", op.node.as_str(), lhs_ty),
+                                lhs_ty_str,
                             );
-                            let mut suggested_deref = false;
-                            if let Ref(_, rty, _) = lhs_ty.kind {
-                                if {
-                                    self.infcx.type_is_copy_modulo_regions(
-                                        self.param_env,
-                                        rty,
-                                        lhs_expr.span,
-                                    ) && self
-                                        .lookup_op_method(rty, &[rhs_ty], Op::Binary(op, is_assign))
-                                        .is_ok()
-                                } {
-                                    if let Ok(lstring) = source_map.span_to_snippet(lhs_expr.span) {
-                                        let msg = &format!(
-                                            "`{}=` can be used on '{}', you can dereference `{}`",
-                                            op.node.as_str(),
-                                            rty.peel_refs(),
-                                            lstring,
-                                        );
-                                        err.span_suggestion(
-                                            lhs_expr.span,
-                                            msg,
-                                            format!("*{}", lstring),
-                                            rustc_errors::Applicability::MachineApplicable,
-                                        );
-                                        suggested_deref = true;
-                                    }
-                                }
+                            if is_visible(lhs_expr.span) {
+                                err.span_label(
+                                    lhs_expr.span,
+                                    format!(
+                                        "cannot use `{}=` on type `{}`",
+                                        op.node.as_str(),
+                                        lhs_ty_str
+                                    ),
+                                );
                             }
-                            let missing_trait = match op.node {
-                                hir::BinOpKind::Add => Some("std::ops::AddAssign"),
-                                hir::BinOpKind::Sub => Some("std::ops::SubAssign"),
-                                hir::BinOpKind::Mul => Some("std::ops::MulAssign"),
-                                hir::BinOpKind::Div => Some("std::ops::DivAssign"),
-                                hir::BinOpKind::Rem => Some("std::ops::RemAssign"),
-                                hir::BinOpKind::BitAnd => Some("std::ops::BitAndAssign"),
-                                hir::BinOpKind::BitXor => Some("std::ops::BitXorAssign"),
-                                hir::BinOpKind::BitOr => Some("std::ops::BitOrAssign"),
-                                hir::BinOpKind::Shl => Some("std::ops::ShlAssign"),
-                                hir::BinOpKind::Shr => Some("std::ops::ShrAssign"),
-                                _ => None,
-                            };
-                            if let Some(missing_trait) = missing_trait {
-                                if op.node == hir::BinOpKind::Add
-                                    && self.check_str_addition(
-                                        lhs_expr, rhs_expr, lhs_ty, rhs_ty, &mut err, true, op,
-                                    )
-                                {
-                                    // This has nothing here because it means we did string
-                                    // concatenation (e.g., "Hello " += "World!"). This means
-                                    // we don't want the note in the else clause to be emitted
-                                } else if let ty::Param(p) = lhs_ty.kind {
-                                    suggest_constraining_param(
-                                        self.tcx,
-                                        self.body_id,
-                                        &mut err,
-                                        lhs_ty,
-                                        rhs_ty,
-                                        missing_trait,
-                                        p,
-                                        false,
-                                    );
-                                } else if !suggested_deref {
-                                    suggest_impl_missing(&mut err, lhs_ty, &missing_trait);
+                            self.label_op_in_macro_call(
+                                &mut err, macro_call, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op,
+                                is_assign,
+                            );
+                            note_long_ty_paths(&mut err, &long_ty_paths);
+                            let param = self.suggest_op_error_fixes(
+                                &mut err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                                expected, false, macro_call,
+                            );
+                            match param {
+                                Some(p) => {
+                                    let constraint = op_trait_path(op, is_assign).to_string();
+                                    self.defer_op_bound_error(err, p, constraint)
                                 }
+                                None => err.emit(),
                             }
-                            err.emit();
                         }
                         IsAssign::No => {
-                            let (message, missing_trait, use_output) = match op.node {
+                            let (message, use_output) = match op.node {
                                 hir::BinOpKind::Add => (
-                                    format!("cannot add `{}` to `{}`", rhs_ty, lhs_ty),
-                                    Some("std::ops::Add"),
+                                    format!("cannot add `{}` to `{}`", rhs_ty_str, lhs_ty_str),
                                     true,
                                 ),
                                 hir::BinOpKind::Sub => (
-                                    format!("cannot subtract `{}` from `{}`", rhs_ty, lhs_ty),
-                                    Some("std::ops::Sub"),
+                                    format!(
+                                        "cannot subtract `{}` from `{}`",
+                                        rhs_ty_str, lhs_ty_str
+                                    ),
                                     true,
                                 ),
                                 hir::BinOpKind::Mul => (
-                                    format!("cannot multiply `{}` to `{}`", rhs_ty, lhs_ty),
-                                    Some("std::ops::Mul"),
+                                    format!("cannot multiply `{}` to `{}`", rhs_ty_str, lhs_ty_str),
                                     true,
                                 ),
                                 hir::BinOpKind::Div => (
-                                    format!("cannot divide `{}` by `{}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::Div"),
+                                    format!("cannot divide `{}` by `{}`", lhs_ty_str, rhs_ty_str),
                                     true,
                                 ),
                                 hir::BinOpKind::Rem => (
-                                    format!("cannot mod `{}` by `{}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::Rem"),
+                                    format!("cannot mod `{}` by `{}`", lhs_ty_str, rhs_ty_str),
                                     true,
                                 ),
                                 hir::BinOpKind::BitAnd => (
-                                    format!("no implementation for `{} & {}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::BitAnd"),
+                                    format!(
+                                        "no implementation for `{} & {}`",
+                                        lhs_ty_str, rhs_ty_str
+                                    ),
                                     true,
                                 ),
                                 hir::BinOpKind::BitXor => (
-                                    format!("no implementation for `{} ^ {}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::BitXor"),
+                                    format!(
+                                        "no implementation for `{} ^ {}`",
+                                        lhs_ty_str, rhs_ty_str
+                                    ),
                                     true,
                                 ),
                                 hir::BinOpKind::BitOr => (
-                                    format!("no implementation for `{} | {}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::BitOr"),
+                                    format!(
+                                        "no implementation for `{} | {}`",
+                                        lhs_ty_str, rhs_ty_str
+                                    ),
                                     true,
                                 ),
                                 hir::BinOpKind::Shl => (
-                                    format!("no implementation for `{} << {}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::Shl"),
-                                    true,
-                                ),
-                                hir::BinOpKind::Shr => (
-                                    format!("no implementation for `{} >> {}`", lhs_ty, rhs_ty),
-                                    Some("std::ops::Shr"),
-                                    true,
-                                ),
-                                hir::BinOpKind::Eq | hir::BinOpKind::Ne => (
                                     format!(
-                                        "binary operation `{}` cannot be applied to type `{}`",
-                                        op.node.as_str(),
-                                        lhs_ty
+                                        "no implementation for `{} << {}`",
+                                        lhs_ty_str, rhs_ty_str
                                     ),
-                                    Some("std::cmp::PartialEq"),
-                                    false,
+                                    true,
                                 ),
-                                hir::BinOpKind::Lt
-                                | hir::BinOpKind::Le
-                                | hir::BinOpKind::Gt
-                                | hir::BinOpKind::Ge => (
+                                hir::BinOpKind::Shr => (
                                     format!(
-                                        "binary operation `{}` cannot be applied to type `{}`",
-                                        op.node.as_str(),
-                                        lhs_ty
+                                        "no implementation for `{} >> {}`",
+                                        lhs_ty_str, rhs_ty_str
                                     ),
-                                    Some("std::cmp::PartialOrd"),
-                                    false,
+                                    true,
                                 ),
                                 _ => (
                                     format!(
                                         "binary operation `{}` cannot be applied to type `{}`",
                                         op.node.as_str(),
-                                        lhs_ty
+                                        lhs_ty_str
                                     ),
-                                    None,
                                     false,
                                 ),
                             };
+                            let missing_trait = op_trait_path(op, is_assign);
                             let mut err = struct_span_err!(
                                 self.tcx.sess,
-                                op.span,
+                                macro_call.unwrap_or(op.span),
                                 E0369,
                                 "{}",
                                 message.as_str()
@@ -431,7 +547,9 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                 involves_fn |= self.add_type_neq_err_label(
                                     &mut err,
                                     lhs_expr.span,
+                                    is_visible(lhs_expr.span),
                                     lhs_ty,
+                                    &lhs_ty_str,
                                     rhs_ty,
                                     op,
                                     is_assign,
@@ -439,83 +557,3611 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                 involves_fn |= self.add_type_neq_err_label(
                                     &mut err,
                                     rhs_expr.span,
+                                    is_visible(rhs_expr.span),
                                     rhs_ty,
+                                    &rhs_ty_str,
                                     lhs_ty,
                                     op,
                                     is_assign,
                                 );
                             }
-
-                            let mut suggested_deref = false;
-                            if let Ref(_, rty, _) = lhs_ty.kind {
-                                if {
-                                    self.infcx.type_is_copy_modulo_regions(
-                                        self.param_env,
-                                        rty,
-                                        lhs_expr.span,
-                                    ) && self
-                                        .lookup_op_method(rty, &[rhs_ty], Op::Binary(op, is_assign))
-                                        .is_ok()
-                                } {
-                                    if let Ok(lstring) = source_map.span_to_snippet(lhs_expr.span) {
-                                        err.help(&format!(
-                                            "`{}` can be used on '{}', you can \
-                                            dereference `{2}`: `*{2}`",
-                                            op.node.as_str(),
-                                            rty.peel_refs(),
-                                            lstring
-                                        ));
-                                        suggested_deref = true;
-                                    }
-                                }
+                            self.label_op_in_macro_call(
+                                &mut err, macro_call, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op,
+                                is_assign,
+                            );
+                            note_long_ty_paths(&mut err, &long_ty_paths);
+                            if let (
+                                hir::BinOpKind::Eq | hir::BinOpKind::Ne,
+                                ty::Closure(..),
+                                ty::Closure(..),
+                            ) = (op.node, &lhs_ty.kind, &rhs_ty.kind)
+                            {
+                                err.note(
+                                    "closures in Rust do not implement `PartialEq`; compare \
+                                     their outputs instead",
+                                );
                             }
-                            if let Some(missing_trait) = missing_trait {
-                                if op.node == hir::BinOpKind::Add
-                                    && self.check_str_addition(
-                                        lhs_expr, rhs_expr, lhs_ty, rhs_ty, &mut err, false, op,
-                                    )
-                                {
-                                    // This has nothing here because it means we did string
-                                    // concatenation (e.g., "Hello " + "World!"). This means
-                                    // we don't want the note in the else clause to be emitted
-                                } else if let ty::Param(p) = lhs_ty.kind {
-                                    suggest_constraining_param(
-                                        self.tcx,
-                                        self.body_id,
-                                        &mut err,
-                                        lhs_ty,
-                                        rhs_ty,
-                                        missing_trait,
-                                        p,
-                                        use_output,
-                                    );
-                                } else if !suggested_deref && !involves_fn {
-                                    suggest_impl_missing(&mut err, lhs_ty, &missing_trait);
+
+                            let param = self.suggest_op_error_fixes(
+                                &mut err,
+                                expr,
+                                lhs_expr,
+                                rhs_expr,
+                                lhs_ty,
+                                rhs_ty,
+                                op,
+                                is_assign,
+                                expected,
+                                involves_fn,
+                                macro_call,
+                            );
+                            match param {
+                                Some(p) => {
+                                    let output = if use_output {
+                                        format!("<Output = {}>", rhs_ty)
+                                    } else {
+                                        String::new()
+                                    };
+                                    let constraint = format!("{}{}", missing_trait, output);
+                                    self.defer_op_bound_error(err, p, constraint)
                                 }
+                                None => err.emit(),
                             }
-                            err.emit();
                         }
                     }
                 }
                 self.tcx.types.err
             }
-        };
+        };
+
+        (lhs_ty, rhs_ty, return_ty)
+    }
+
+    /// Adds the suggestions and notes for an operator without an applicable implementation, for
+    /// both `a op b` and `a op= b`, written in the macro call `macro_call` if any. Returns the type
+    /// parameter on the LHS, if any, that is missing a bound for the operator.
+    fn suggest_op_error_fixes(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+        expected: Expectation<'tcx>,
+        involves_fn: bool,
+        macro_call: Option<Span>,
+    ) -> Option<ty::ParamTy> {
+        let is_binary = is_assign == IsAssign::No;
+        // Within a macro call, only the operands that are arguments of the call can be rewritten,
+        // and the operation as a whole can't be.
+        let in_macro_call = macro_call.is_some();
+        let on_operands =
+            !in_macro_call || !lhs_expr.span.from_expansion() && !rhs_expr.span.from_expansion();
+        let mut suggested_deref = false;
+        if on_operands {
+            if let Ref(_, rty, _) = lhs_ty.kind {
+                if self.infcx.type_is_copy_modulo_regions(self.param_env, rty, lhs_expr.span)
+                    && self.lookup_op_method(rty, &[rhs_ty], Op::Binary(op, is_assign)).is_ok()
+                {
+                    let source_map = self.tcx.sess.source_map();
+                    if let Ok(lstring) = source_map.span_to_snippet(lhs_expr.span) {
+                        let msg = &format!(
+                            "`{}{}` can be used on '{}', you can dereference `{}`",
+                            op.node.as_str(),
+                            if is_binary { "" } else { "=" },
+                            rty.peel_refs(),
+                            lstring,
+                        );
+                        err.span_suggestion(
+                            lhs_expr.span,
+                            msg,
+                            deref_snippet(lhs_expr, lstring),
+                            Applicability::MachineApplicable,
+                        );
+                        suggested_deref = true;
+                    }
+                }
+            }
+            suggested_deref = suggested_deref
+                || self.suggest_deref_pointer_operands(
+                    err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                )
+                || self.suggest_shared_borrow_of_rhs(err, rhs_expr, lhs_ty, rhs_ty, op, is_assign)
+                || self.note_maybe_uninit_operands(
+                    err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                )
+                || self.suggest_unpinning_operands(
+                    err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                )
+                || self.suggest_locking_operands(
+                    err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                )
+                || self.suggest_cell_get_operands(
+                    err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                );
+        }
+        let suggested_deref = suggested_deref
+            || is_binary
+                && !in_macro_call
+                && self.suggest_option_arithmetic(
+                    err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, expected,
+                )
+            || is_binary
+                && on_operands
+                && self.suggest_swapped_operands(err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+
+        let mut param = None;
+        if op.node == hir::BinOpKind::Add
+            && self.check_str_addition(lhs_expr, rhs_expr, lhs_ty, rhs_ty, err, !is_binary, op)
+        {
+            // This has nothing here because it means we did string
+            // concatenation (e.g., "Hello " + "World!"). This means
+            // we don't want the note in the else clause to be emitted
+        } else if let ty::Param(p) = lhs_ty.kind {
+            let missing_trait = op_trait_path(op, is_assign);
+            self.check_op_on_type_param_with_default(err, p, rhs_ty, op, is_assign, missing_trait);
+            param = Some(p);
+        } else if !suggested_deref && !involves_fn {
+            if on_operands {
+                self.check_op_on_str_slice(err, lhs_expr, lhs_ty, op, is_assign);
+                self.check_op_on_char(err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+            }
+            self.check_op_on_dyn_trait(err, lhs_ty, rhs_ty, op, is_assign);
+            self.note_simd_shift_by_scalar_gate(err, lhs_ty, rhs_ty, op);
+            if !(is_binary && self.suggest_impl_missing_for_ref(err, lhs_ty, rhs_ty, op))
+                && !self.suggest_operator_impl_for_newtype(err, lhs_ty, rhs_ty, op, is_assign)
+            {
+                suggest_impl_missing(self.tcx, err, lhs_ty, op_trait_path(op, is_assign));
+            }
+            if on_operands {
+                self.diagnose_op_for_tuple(err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign);
+            }
+            if !in_macro_call {
+                self.suggest_vec_concat(
+                    err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                );
+                self.suggest_map_extend(
+                    err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op, is_assign,
+                );
+            }
+            if is_binary && !in_macro_call {
+                self.suggest_slice_concat(err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+                self.suggest_iterator_chain(err, expr, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+            }
+            if is_binary && on_operands {
+                self.suggest_borrowed_set_op(err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+                self.note_array_elementwise_op(err, lhs_expr, rhs_expr, lhs_ty, rhs_ty, op);
+            }
+        }
+        if on_operands {
+            // The LHS of an assignment operation is a place, which can't be awaited.
+            if is_binary {
+                self.suggest_await_on_operands(
+                    err,
+                    &[(lhs_expr, lhs_ty), (rhs_expr, rhs_ty)],
+                    op,
+                    is_assign,
+                );
+            } else {
+                self.suggest_await_on_operands(err, &[(rhs_expr, rhs_ty)], op, is_assign);
+            }
+        }
+        param
+    }
+
+    /// Without `#![feature(simd_shift_by_scalar)]`, shifting an integer vector by a scalar amount
+    /// isn't builtin; say which feature makes it so.
+    fn note_simd_shift_by_scalar_gate(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if matches!(BinOpCategory::from(op), BinOpCategory::Shift)
+            && !self.tcx.features().simd_shift_by_scalar
+            && is_integer_vector(self.tcx, lhs_ty)
+            && rhs_ty.is_integral()
+        {
+            err.help(
+                "add `#![feature(simd_shift_by_scalar)]` to the crate attributes to enable \
+                shifting every lane of an integer vector by a scalar amount",
+            );
+        }
+    }
+
+    /// Labels the call of the macro that an erroneous operation was written in with the operator
+    /// and the operand types, when the macro wrote an operand itself, like the comparison of
+    /// `*left_val` and `*right_val` in `assert_eq!(a, b)`, so the operand can't be pointed at.
+    fn label_op_in_macro_call(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        macro_call: Option<Span>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        let call_site = match macro_call {
+            Some(call_site) => call_site,
+            None => return,
+        };
+        if !lhs_expr.span.from_expansion() && !rhs_expr.span.from_expansion() {
+            return;
+        }
+        let op =
+            format!("{}{}", op.node.as_str(), if is_assign == IsAssign::Yes { "=" } else { "" });
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let operands = if lhs_ty == rhs_ty {
+            format!("two values of type `{}`", lhs_ty)
+        } else {
+            format!("`{}` and `{}`", lhs_ty, rhs_ty)
+        };
+        err.span_label(call_site, format!("`{}` is applied to {} in this macro", op, operands));
+    }
+
+    /// Explains that the unsized `str` is not the `&str` that is usually meant, when an operator is
+    /// applied to a `str` itself, e.g. with `*s + "!"` or `s[1..] + "!"`.
+    fn check_op_on_str_slice(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        if self.resolve_vars_if_possible(&lhs_ty).kind != Str {
+            return;
+        }
+        err.note(
+            "`str` is the unsized string slice type, which is used behind a reference, as in \
+             `&str`, instead of directly",
+        );
+        if op.node != hir::BinOpKind::Add || is_assign == IsAssign::Yes {
+            return;
+        }
+        if let Ok(lhs) = self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+            let receiver = parenthesize(lhs_expr, lhs, PREC_POSTFIX);
+            err.span_suggestion(
+                lhs_expr.span,
+                "to concatenate strings, create an owned `String` from the `str`",
+                format!("{}.to_owned()", receiver),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Explains how to do arithmetic on a `char`, e.g. `'a' + 1`, through its code point.
+    fn check_op_on_char(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if self.resolve_vars_if_possible(&lhs_ty).kind != Char
+            || !matches!(BinOpCategory::from(op), BinOpCategory::Math)
+        {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let operand = |expr: &hir::Expr<'_>| {
+            source_map
+                .span_to_snippet(expr.span)
+                .ok()
+                .map(|snippet| parenthesize(expr, snippet, PREC_PREFIX))
+        };
+        let (lhs, rhs) = match (operand(lhs_expr), operand(rhs_expr)) {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => {
+                err.note("character arithmetic is not supported");
+                return;
+            }
+        };
+        let op_str = op.node.as_str();
+        let from_u32 = format!(
+            "`std::char::from_u32({} as u32 {} {})`, which is `None` if the result isn't a valid \
+             `char`",
+            lhs, op_str, rhs
+        );
+        let from_u8 = format!(
+            "`({0} as u8 {1} {2}) as char` if `{0}` is an ASCII character",
+            lhs, op_str, rhs
+        );
+        let rhs_ty = self.resolve_vars_if_possible(&rhs_ty);
+        let additive = matches!(op.node, hir::BinOpKind::Add | hir::BinOpKind::Sub);
+        let note = match rhs_ty.kind {
+            Char if op.node == hir::BinOpKind::Sub => format!(
+                "characters can't be subtracted; to get the distance between them, subtract their \
+                 code points: `{} as u32 - {} as u32`",
+                lhs, rhs
+            ),
+            ty::Infer(ty::IntVar(_)) if additive => {
+                format!("character arithmetic is not supported; use {}, or {}", from_u32, from_u8)
+            }
+            Uint(ast::UintTy::U32) if additive => {
+                format!("character arithmetic is not supported; use {}", from_u32)
+            }
+            Uint(ast::UintTy::U8) if additive => {
+                format!("character arithmetic is not supported; use {}", from_u8)
+            }
+            _ => format!(
+                "character arithmetic is not supported; convert the `char` to its code point \
+                 first, with `{} as u32`",
+                lhs
+            ),
+        };
+        err.note(&note);
+    }
+
+    /// Explains that a trait object like `dyn Shape` only supports the operators its trait
+    /// requires, and that an operator trait can't be added to it as an extra bound, e.g. for
+    /// `Box<dyn Shape> + Box<dyn Shape>`. For a local trait behind a pointer, also points out that
+    /// the operator can be implemented for the pointer type itself.
+    fn check_op_on_dyn_trait(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        let peel = |ty: Ty<'tcx>| {
+            let ty = self.resolve_vars_if_possible(&ty).peel_refs();
+            if ty.is_box() { ty.boxed_ty().peel_refs() } else { ty }
+        };
+        let ty = peel(lhs_ty);
+        let principal_def_id = match ty.kind {
+            ty::Dynamic(predicates, _) => match predicates.principal_def_id() {
+                Some(def_id) => def_id,
+                None => return,
+            },
+            _ => return,
+        };
+        let principal = self.tcx.def_path_str(principal_def_id);
+        let object = format!("dyn {}", principal);
+        let missing_trait = op_trait_path(op, is_assign);
+        err.note(&format!(
+            "`{}` only supports the operators that `{}` requires through its supertraits, and \
+             `{}` can't be added to it as an extra bound, since only auto traits can",
+            object, principal, missing_trait,
+        ));
+        let mut args = vec![];
+        if peel(rhs_ty) != ty {
+            args.push(rhs_ty.to_string());
+        }
+        if is_assign == IsAssign::No
+            && !matches!(BinOpCategory::from(op), BinOpCategory::Comparison)
+        {
+            args.push("Output = T".to_string());
+        }
+        let bound = if args.is_empty() {
+            format!("{} + {}", principal, missing_trait)
+        } else {
+            format!("{} + {}<{}>", principal, missing_trait, args.join(", "))
+        };
+        err.help(&format!(
+            "consider adding a method to `{}` that performs the operation, or using a type \
+             parameter `T: {}` instead of `{}`",
+            principal, bound, object,
+        ));
+        let lhs_ty = self.resolve_vars_if_possible(&lhs_ty);
+        if lhs_ty != ty && principal_def_id.is_local() {
+            err.help(&format!(
+                "`{}` is defined in the current crate, so `{}` can also be implemented for `{}`, \
+                 dispatching to the methods of `{}`",
+                principal, missing_trait, lhs_ty, principal,
+            ));
+        }
+    }
+
+    /// Returns the contents of a `ManuallyDrop` if they are `Copy`, so that they can be read
+    /// through its `Deref` implementation.
+    fn manually_drop_contents(&self, ty: Ty<'tcx>, span: Span) -> Option<Ty<'tcx>> {
+        match ty.kind {
+            Adt(def, substs) if Some(def.did) == self.tcx.lang_items().manually_drop() => {
+                let contents = substs.type_at(0);
+                if self.infcx.type_is_copy_modulo_regions(self.param_env, contents, span) {
+                    Some(contents)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        }
+    }
+
+    /// Suggests dereferencing the operand of a unary operator that is a `ManuallyDrop`, if the
+    /// operator can be applied to its contents.
+    fn suggest_deref_manually_drop_operand(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        ex: &'tcx hir::Expr<'tcx>,
+        operand_ty: Ty<'tcx>,
+        op: hir::UnOp,
+    ) {
+        let operand = match ex.kind {
+            hir::ExprKind::Unary(_, operand) => operand,
+            _ => return,
+        };
+        let contents = match self.manually_drop_contents(operand_ty, operand.span) {
+            Some(contents) => contents,
+            None => return,
+        };
+        if self.lookup_op_method(contents, &[], Op::Unary(op, ex.span)).is_err() {
+            return;
+        }
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(operand.span) {
+            let deref = deref_snippet(operand, snippet);
+            err.span_suggestion_verbose(
+                operand.span,
+                "consider dereferencing the `ManuallyDrop`",
+                deref,
+                Applicability::MachineApplicable,
+            );
+        }
+    }
+
+    /// Suggests reading the operand of a unary operator that is a `Cell` with `get`, if the
+    /// operator can be applied to its contents.
+    fn suggest_cell_get_for_unop(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        ex: &'tcx hir::Expr<'tcx>,
+        operand_ty: Ty<'tcx>,
+        op: hir::UnOp,
+    ) {
+        let operand = match ex.kind {
+            hir::ExprKind::Unary(_, operand) => operand,
+            _ => return,
+        };
+        let contents = match self.cell_contents(operand_ty) {
+            Some(contents) => contents,
+            None => return,
+        };
+        if self.lookup_op_method(contents, &[], Op::Unary(op, ex.span)).is_ok() {
+            self.suggest_cell_get(err, &[(operand, contents)]);
+        }
+    }
+
+    /// Suggests an implementation of the missing operator for a newtype `lhs_ty`, whose single
+    /// field implements it, that applies the operator to that field. Returns `true` if the
+    /// suggestion was given.
+    fn suggest_operator_impl_for_newtype(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let lhs_ty = self.resolve_vars_if_possible(&lhs_ty);
+        if op.node.is_comparison() || lhs_ty != self.resolve_vars_if_possible(&rhs_ty) {
+            return false;
+        }
+        let def = match lhs_ty.kind {
+            Adt(def, substs) if def.is_struct() && def.did.is_local() && substs.is_empty() => def,
+            _ => return false,
+        };
+        let variant = def.non_enum_variant();
+        let field = match &variant.fields[..] {
+            [field] => field,
+            _ => return false,
+        };
+        let field_ty = self.tcx.type_of(field.did);
+        match self.lookup_op_method(field_ty, &[field_ty], Op::Binary(op, is_assign)) {
+            Ok(_) if is_assign == IsAssign::Yes => {}
+            Ok(method) if self.resolve_vars_if_possible(&method.sig.output()) == field_ty => {}
+            _ => return false,
+        }
+        let source_map = self.tcx.sess.source_map();
+        let span = match self.tcx.hir().span_if_local(def.did) {
+            Some(span) if !span.from_expansion() => span,
+            _ => return false,
+        };
+        let indent = " ".repeat(source_map.span_to_margin(span).unwrap_or(0));
+
+        let missing_trait = op_trait_path(op, is_assign);
+        let method = op_method_name(op, is_assign);
+        let (lhs, rhs) = (format!("self.{}", field.ident), format!("rhs.{}", field.ident));
+        let body = match (is_assign, variant.ctor_kind) {
+            (IsAssign::Yes, _) => format!("{} {}= {};", lhs, op.node.as_str(), rhs),
+            (IsAssign::No, CtorKind::Fn) => format!("Self({} {} {})", lhs, op.node.as_str(), rhs),
+            (IsAssign::No, _) => {
+                format!("Self {{ {}: {} {} {} }}", field.ident, lhs, op.node.as_str(), rhs)
+            }
+        };
+        let items = match is_assign {
+            IsAssign::Yes => vec![
+                format!("fn {}(&mut self, rhs: Self) {{", method),
+                format!("    {}", body),
+                "}".to_string(),
+            ],
+            IsAssign::No => vec![
+                "type Output = Self;".to_string(),
+                String::new(),
+                format!("fn {}(self, rhs: Self) -> Self {{", method),
+                format!("    {}", body),
+                "}".to_string(),
+            ],
+        };
+        let mut sugg = format!("\n\n{}impl {} for {} {{\n", indent, missing_trait, lhs_ty);
+        for item in items {
+            if !item.is_empty() {
+                sugg.push_str(&format!("{}    {}", indent, item));
+            }
+            sugg.push('\n');
+        }
+        sugg.push_str(&format!("{}}}", indent));
+
+        err.note(&format!(
+            "an implementation of `{}` might be missing for `{}`",
+            missing_trait, lhs_ty
+        ));
+        err.span_suggestion_verbose(
+            span.shrink_to_hi(),
+            &format!(
+                "`{}` implements `{}`, so an implementation for `{}` could apply it to the field",
+                field_ty, missing_trait, lhs_ty,
+            ),
+            sugg,
+            Applicability::MaybeIncorrect,
+        );
+        true
+    }
+
+    /// Suggests dereferencing the operands that are boxed numbers, `Rc`s and `Arc`s or
+    /// `ManuallyDrop`s, if the operator can be applied to what they point to. Returns `true` if the
+    /// suggestion was given.
+    fn suggest_deref_pointer_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        // The name of the pointer and the type it points to.
+        let pointee = |ty: Ty<'tcx>, span: Span| {
+            let ty = self.resolve_vars_if_possible(&ty);
+            match ty.kind {
+                _ if ty.is_box() && ty.boxed_ty().is_numeric() => Some(("box", ty.boxed_ty())),
+                Adt(def, substs) if self.tcx.is_diagnostic_item(sym::Rc, def.did) => {
+                    Some(("`Rc`", substs.type_at(0)))
+                }
+                Adt(def, substs) if self.tcx.is_diagnostic_item(sym::Arc, def.did) => {
+                    Some(("`Arc`", substs.type_at(0)))
+                }
+                _ => self.manually_drop_contents(ty, span).map(|ty| ("`ManuallyDrop`", ty)),
+            }
+        };
+        let (lhs_pointee, rhs_pointee) =
+            (pointee(lhs_ty, lhs_expr.span), pointee(rhs_ty, rhs_expr.span));
+        if lhs_pointee.is_none() && rhs_pointee.is_none() {
+            return false;
+        }
+        if self
+            .lookup_op_method(
+                lhs_pointee.map_or(lhs_ty, |(_, ty)| ty),
+                &[rhs_pointee.map_or(rhs_ty, |(_, ty)| ty)],
+                Op::Binary(op, is_assign),
+            )
+            .is_err()
+        {
+            return false;
+        }
+        if let (IsAssign::Yes, Some((pointer, pointee))) = (is_assign, lhs_pointee) {
+            // `*rc += 1` doesn't work either, the contents are only borrowed immutably.
+            if matches!(pointer, "`Rc`" | "`Arc`") {
+                err.note(&format!(
+                    "the contents of an {} can't be modified, because they may be shared",
+                    pointer,
+                ));
+                err.help(&format!(
+                    "to modify a shared value, use `{0}<Cell<{1}>>` or `{0}<RefCell<{1}>>` instead",
+                    pointer.trim_matches('`'),
+                    pointee,
+                ));
+                return true;
+            }
+        }
+        // `Deref` makes the methods of the contents callable on the pointer, but not the operators.
+        let shared_pointer = [lhs_pointee, rhs_pointee]
+            .iter()
+            .flatten()
+            .map(|&(pointer, _)| pointer)
+            .find(|&pointer| pointer != "box");
+        if let Some(pointer) = shared_pointer {
+            err.note(&format!(
+                "operators aren't forwarded to the value an {} points to, unlike method calls",
+                pointer
+            ));
+        }
+        let source_map = self.tcx.sess.source_map();
+        // The snippet of each operand, and how it is dereferenced if it is a pointer.
+        let mut operands = vec![];
+        for &(expr, pointee) in &[(lhs_expr, lhs_pointee), (rhs_expr, rhs_pointee)] {
+            let snippet = match source_map.span_to_snippet(expr.span) {
+                Ok(snippet) => snippet,
+                Err(_) => return false,
+            };
+            let deref = pointee.map(|(pointer, pointee)| {
+                let deref = deref_snippet(expr, snippet);
+                // Only the contents of a box can be moved out of it.
+                let is_copy = pointer == "box"
+                    || self.infcx.type_is_copy_modulo_regions(self.param_env, pointee, expr.span);
+                (pointer, deref, is_copy)
+            });
+            operands.push((expr.span, snippet, deref));
+        }
+        let not_copy = operands.iter().find_map(|(_, _, deref)| match deref {
+            Some((pointer, _, false)) => Some(*pointer),
+            _ => None,
+        });
+        if let Some(pointer) = not_copy {
+            let written: Vec<_> = operands
+                .iter()
+                .map(|(_, snippet, deref)| match deref {
+                    Some((_, deref, true)) => deref.clone(),
+                    Some((_, deref, false)) => format!("({}).clone()", deref),
+                    None => snippet.clone(),
+                })
+                .collect();
+            err.note(&format!(
+                "the contents of an {} can't be moved out of it, but they can be cloned: \
+                 `{} {}{} {}`",
+                pointer,
+                written[0],
+                op.node.as_str(),
+                if is_assign == IsAssign::Yes { "=" } else { "" },
+                written[1],
+            ));
+            return true;
+        }
+        let derefs: Vec<_> = operands
+            .into_iter()
+            .filter_map(|(span, _, deref)| deref.map(|(pointer, deref, _)| (span, pointer, deref)))
+            .collect();
+        let msg = match &derefs[..] {
+            [(_, pointer, _)] => format!("consider dereferencing the {}", pointer),
+            [(_, "box", _), (_, "box", _)] => "consider dereferencing the boxes".to_string(),
+            [(_, lhs_pointer, _), (_, rhs_pointer, _)] if lhs_pointer == rhs_pointer => {
+                format!("consider dereferencing the {}s", lhs_pointer)
+            }
+            _ => "consider dereferencing both operands".to_string(),
+        };
+        if let [(span, _, deref)] = &derefs[..] {
+            err.span_suggestion_verbose(
+                *span,
+                &msg,
+                deref.clone(),
+                Applicability::MachineApplicable,
+            );
+        } else {
+            err.multipart_suggestion(
+                &msg,
+                derefs.into_iter().map(|(span, _, deref)| (span, deref)).collect(),
+                Applicability::MachineApplicable,
+            );
+        }
+        true
+    }
+
+    /// Like `suggest_impl_missing`, for the reference `lhs_ty` to a local type that implements the
+    /// operator itself: shows how an implementation for the reference could delegate to the one for
+    /// the referenced type. Returns `true` if the suggestion was given.
+    fn suggest_impl_missing_for_ref(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) -> bool {
+        let referent = match lhs_ty.kind {
+            Ref(_, referent, _) if !op.node.is_comparison() => referent,
+            _ => return false,
+        };
+        if !matches!(referent.kind, Adt(def, _) if def.did.is_local()) {
+            return false;
+        }
+        let output = match self.lookup_op_method(
+            referent,
+            &[rhs_ty.peel_refs()],
+            Op::Binary(op, IsAssign::No),
+        ) {
+            Ok(method) => method.sig.output(),
+            Err(()) => return false,
+        };
+        // The implementation is written out with these types, so they have to be fully known
+        // and nameable outside of the current function.
+        let nameable = |ty: Ty<'tcx>| {
+            let ty = self.tcx.erase_regions(&self.resolve_vars_if_possible(&ty));
+            if ty.needs_infer() || ty.has_param_types_or_consts() {
+                None
+            } else {
+                Some(ty)
+            }
+        };
+        let (lhs_ty, rhs_ty, output) = match (nameable(lhs_ty), nameable(rhs_ty), nameable(output))
+        {
+            (Some(lhs_ty), Some(rhs_ty), Some(output)) => (lhs_ty, rhs_ty, output),
+            _ => return false,
+        };
+        let missing_trait = op_trait_path(op, IsAssign::No);
+        let (trait_ref, rhs_param_ty) = if rhs_ty == lhs_ty {
+            (missing_trait.to_string(), "Self".to_string())
+        } else {
+            (format!("{}<{}>", missing_trait, rhs_ty), rhs_ty.to_string())
+        };
+        // The operands are read out of the references with `*` if they are `Copy` and cloned if
+        // they are only `Clone`. Otherwise, `clone` would clone the reference, and the
+        // implementation for the reference would call itself.
+        let clone_trait = self.tcx.lang_items().clone_trait();
+        let owned_value = |ty: Ty<'tcx>, name: &str| match ty.kind {
+            Ref(_, referent, _) => {
+                if self.infcx.type_is_copy_modulo_regions(self.param_env, referent, op.span) {
+                    Some(format!("*{}", name))
+                } else if clone_trait.map_or(false, |clone_trait| {
+                    self.tcx.type_implements_trait((
+                        clone_trait,
+                        referent,
+                        ty::List::empty(),
+                        self.param_env,
+                    ))
+                }) {
+                    Some(format!("{}.clone()", name))
+                } else {
+                    None
+                }
+            }
+            _ => Some(name.to_string()),
+        };
+        let (self_value, rhs_value) =
+            match (owned_value(lhs_ty, "self"), owned_value(rhs_ty, "rhs")) {
+                (Some(self_value), Some(rhs_value)) => (self_value, rhs_value),
+                _ => return false,
+            };
+        err.note(&format!(
+            "an implementation of `{}` might be missing for `{}`",
+            missing_trait, lhs_ty
+        ));
+        err.help(&format!(
+            "`{}` implements `{}`, so an implementation for `{}` could delegate to it: `impl {} \
+             for {} {{ type Output = {}; fn {}(self, rhs: {}) -> {} {{ {} {} {} }} }}`",
+            referent,
+            missing_trait,
+            lhs_ty,
+            trait_ref,
+            lhs_ty,
+            output,
+            op_method_name(op, IsAssign::No),
+            rhs_param_ty,
+            output,
+            self_value,
+            op.node.as_str(),
+            rhs_value,
+        ));
+        true
+    }
+
+    /// Suggests awaiting the operands that are futures, if we are in an `async` body. Futures
+    /// don't implement any operators, but their outputs might.
+    fn suggest_await_on_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        operands: &[(&'tcx hir::Expr<'tcx>, Ty<'tcx>)],
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        // `.await` is not permitted outside of `async` bodies.
+        let hir = self.tcx.hir();
+        let is_async = hir
+            .maybe_body_owned_by(hir.get_parent_node(self.body_id))
+            .map(|body_id| hir.body(body_id).generator_kind)
+            .map_or(false, |kind| matches!(kind, Some(hir::GeneratorKind::Async(_))));
+        if !is_async {
+            return;
+        }
+        let future_trait = match self.tcx.lang_items().future_trait() {
+            Some(future_trait) => future_trait,
+            None => return,
+        };
+        let mut suggested = false;
+        for &(expr, ty) in operands {
+            let ty = self.resolve_vars_if_possible(&ty);
+            // Inference variables can't be checked for implementing `Future`.
+            if ty.has_infer_types() {
+                continue;
+            }
+            let ty = self.tcx.erase_regions(&ty);
+            if !self.tcx.type_implements_trait((
+                future_trait,
+                ty,
+                ty::List::empty(),
+                self.param_env,
+            )) {
+                continue;
+            }
+            if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(expr.span) {
+                let snippet = parenthesize(expr, snippet, PREC_POSTFIX);
+                err.span_suggestion_verbose(
+                    expr.span,
+                    "consider using `.await` here",
+                    format!("{}.await", snippet),
+                    Applicability::MaybeIncorrect,
+                );
+                suggested = true;
+            }
+        }
+        if suggested {
+            err.note(&format!(
+                "`{}{}` can't be applied to a future, only to its output",
+                op.node.as_str(),
+                if let IsAssign::Yes = is_assign { "=" } else { "" },
+            ));
+        }
+    }
+
+    /// Notes how to apply an arithmetic, bitwise or shift operator to two tuples element by
+    /// element. The standard library doesn't implement these operators for tuples, and coherence
+    /// rules out implementing them anywhere else.
+    fn diagnose_op_for_tuple(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        if matches!(
+            BinOpCategory::from(op),
+            BinOpCategory::Shortcircuit | BinOpCategory::Comparison
+        ) {
+            return;
+        }
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let (lhs_tys, rhs_tys) = match (&lhs_ty.kind, &rhs_ty.kind) {
+            (Tuple(lhs_tys), Tuple(rhs_tys)) if !lhs_tys.is_empty() => (lhs_tys, rhs_tys),
+            _ => return,
+        };
+        // Only suggest this when it's sure to work, which is when each element is a builtin type
+        // the operator applies to.
+        if lhs_tys.len() != rhs_tys.len()
+            || lhs_tys
+                .types()
+                .zip(rhs_tys.types())
+                .any(|(lhs_ty, rhs_ty)| !is_builtin_binop(self.tcx, lhs_ty, rhs_ty, op))
+        {
+            return;
+        }
+        let elements: Option<Vec<_>> = (0..lhs_tys.len())
+            .map(|i| {
+                let lhs = self.tuple_element_snippet(lhs_expr, i)?;
+                let rhs = self.tuple_element_snippet(rhs_expr, i)?;
+                Some(match is_assign {
+                    IsAssign::Yes => format!("{} {}= {};", lhs, op.node.as_str(), rhs),
+                    IsAssign::No => format!("{} {} {}", lhs, op.node.as_str(), rhs),
+                })
+            })
+            .collect();
+        let elements = match elements {
+            Some(elements) => elements,
+            None => return,
+        };
+        let code = match (is_assign, &elements[..]) {
+            (IsAssign::Yes, _) => elements.join(" "),
+            (IsAssign::No, [element]) => format!("({},)", element),
+            (IsAssign::No, _) => format!("({})", elements.join(", ")),
+        };
+        err.note(&format!(
+            "tuples don't implement `{}`, but `{}{}` can be applied to each of their elements: \
+             `{}`",
+            op_trait_path(op, is_assign),
+            op.node.as_str(),
+            if let IsAssign::Yes = is_assign { "=" } else { "" },
+            code,
+        ));
+    }
+
+    /// Returns the source of the element `index` of the tuple `expr`. Only tuple expressions and
+    /// places are supported, since other operands would be evaluated once per element.
+    fn tuple_element_snippet(&self, expr: &hir::Expr<'_>, index: usize) -> Option<String> {
+        let source_map = self.tcx.sess.source_map();
+        match expr.kind {
+            hir::ExprKind::Tup(elements) => {
+                let element = &elements[index];
+                let snippet = source_map.span_to_snippet(element.span).ok()?;
+                Some(parenthesize(element, snippet, PREC_POSTFIX))
+            }
+            hir::ExprKind::Path(..) | hir::ExprKind::Field(..) => {
+                let snippet = source_map.span_to_snippet(expr.span).ok()?;
+                Some(format!("{}.{}", snippet, index))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the operator is implemented for a shared reference as its RHS, but the RHS is a
+    /// mutable reference or not a reference at all, like `a + &mut b` or `a + b` with
+    /// `impl Add<&B> for A`.
+    fn is_op_only_implemented_for_shared_rhs(
+        &self,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let rhs_ty = self.resolve_vars_if_possible(&rhs_ty);
+        let shared_rhs_ty = match rhs_ty.kind {
+            Ref(region, pointee, hir::Mutability::Mut) => self.tcx.mk_imm_ref(region, pointee),
+            Ref(..) | ty::Infer(ty::TyVar(_)) | ty::Error => return false,
+            _ => {
+                let region = self.next_region_var(RegionVariableOrigin::MiscVariable(op.span));
+                self.tcx.mk_imm_ref(region, rhs_ty)
+            }
+        };
+        !self.op_may_apply(lhs_ty, &[rhs_ty], Op::Binary(op, is_assign))
+            && self.op_may_apply(lhs_ty, &[shared_rhs_ty], Op::Binary(op, is_assign))
+    }
+
+    /// Suggests borrowing the RHS immutably, or reborrowing it if it is a mutable reference, when
+    /// the operator is only implemented for a shared reference. Returns whether a suggestion was
+    /// made.
+    fn suggest_shared_borrow_of_rhs(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        if !self.is_op_only_implemented_for_shared_rhs(lhs_ty, rhs_ty, op, is_assign) {
+            return false;
+        }
+        let rhs_ty = self.resolve_vars_if_possible(&rhs_ty);
+        let (shared_rhs_ty, reborrow) = match rhs_ty.kind {
+            Ref(region, pointee, _) => (self.tcx.mk_imm_ref(region, pointee), "&*"),
+            _ => (self.tcx.mk_imm_ref(self.tcx.lifetimes.re_erased, rhs_ty), "&"),
+        };
+        let msg = format!(
+            "`{}<{}>` is implemented for `{}`, consider borrowing immutably",
+            op_trait_path(op, is_assign),
+            shared_rhs_ty,
+            lhs_ty
+        );
+        match rhs_expr.kind {
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, hir::Mutability::Mut, inner) => {
+                err.span_suggestion_verbose(
+                    rhs_expr.span.until(inner.span),
+                    &msg,
+                    "&".to_string(),
+                    Applicability::MachineApplicable,
+                );
+            }
+            _ => {
+                let rhs = match self.tcx.sess.source_map().span_to_snippet(rhs_expr.span) {
+                    Ok(rhs) => rhs,
+                    Err(_) => return false,
+                };
+                let rhs = parenthesize(rhs_expr, rhs, PREC_PREFIX);
+                err.span_suggestion_verbose(
+                    rhs_expr.span,
+                    &msg,
+                    format!("{}{}", reborrow, rhs),
+                    Applicability::MachineApplicable,
+                );
+            }
+        }
+        true
+    }
+
+    /// Explains that `MaybeUninit` operands have to be extracted before an operator can apply to
+    /// their value, and suggests `assume_init` for the operands that are read. Returns whether any
+    /// operand is a `MaybeUninit`.
+    fn note_maybe_uninit_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let is_maybe_uninit = |ty: Ty<'tcx>| match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, _) => self.tcx.is_diagnostic_item(sym::MaybeUninit, def.did),
+            _ => false,
+        };
+        let operands = [(lhs_expr, lhs_ty), (rhs_expr, rhs_ty)];
+        let uninit_operands = operands
+            .iter()
+            .filter(|&&(_, ty)| is_maybe_uninit(ty))
+            .map(|&(expr, ty)| (expr, self.resolve_vars_if_possible(&ty)))
+            .collect::<Vec<_>>();
+        if uninit_operands.is_empty() {
+            return false;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let mut suggestions = vec![];
+        for &(expr, ty) in &uninit_operands {
+            let snippet = match source_map.span_to_snippet(expr.span) {
+                Ok(snippet) => snippet,
+                Err(_) => continue,
+            };
+            err.note(&format!(
+                "`{}` is a `{}`, which may not be initialized, so its value has to be extracted \
+                 before `{}` can be applied to it",
+                snippet,
+                ty,
+                op.node.as_str()
+            ));
+            // The place assigned to by a compound assignment can't be replaced by its value.
+            if is_assign == IsAssign::Yes && expr.hir_id == lhs_expr.hir_id {
+                continue;
+            }
+            let receiver = parenthesize(expr, snippet, PREC_POSTFIX);
+            suggestions.push((expr.span, format!("unsafe {{ {}.assume_init() }}", receiver)));
+        }
+        if suggestions.is_empty() {
+            err.help("initialize the value earlier instead, without `MaybeUninit`");
+            return true;
+        }
+        err.note(
+            "calling `assume_init` on a value that isn't initialized yet is undefined behavior; \
+             if that can happen, initialize the value earlier instead, without `MaybeUninit`",
+        );
+        let msg = "if the value is known to be initialized, extract it with `assume_init`";
+        if suggestions.len() == 1 {
+            let (span, sugg) = suggestions.remove(0);
+            err.span_suggestion_verbose(span, msg, sugg, Applicability::MaybeIncorrect);
+        } else {
+            err.multipart_suggestion(msg, suggestions, Applicability::MaybeIncorrect);
+        }
+        true
+    }
+
+    /// Explains that `Pin` doesn't forward operators to the value it points to, and suggests
+    /// reading that value with `get_ref`, or modifying it with `get_mut` if it is `Unpin`. Returns
+    /// `true` if the note was given.
+    fn suggest_unpinning_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        // The type a `Pin<&T>`, `Pin<&mut T>` or `Pin<Box<T>>` points to, and whether the pointer
+        // allows modifying it.
+        let pinned = |ty: Ty<'tcx>| match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, substs) if Some(def.did) == self.tcx.lang_items().pin_type() => {
+                let pointer = substs.type_at(0);
+                match pointer.kind {
+                    Ref(_, ty, mutbl) => Some((ty, mutbl)),
+                    _ if pointer.is_box() => Some((pointer.boxed_ty(), hir::Mutability::Mut)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        };
+        let (lhs_pinned, rhs_pinned) = (pinned(lhs_ty), pinned(rhs_ty));
+        if lhs_pinned.is_none() && rhs_pinned.is_none() {
+            return false;
+        }
+        let (lhs_inner_ty, rhs_inner_ty) =
+            (lhs_pinned.map_or(lhs_ty, |(ty, _)| ty), rhs_pinned.map_or(rhs_ty, |(ty, _)| ty));
+        if self.lookup_op_method(lhs_inner_ty, &[rhs_inner_ty], Op::Binary(op, is_assign)).is_err()
+        {
+            // A generic pinned type may just be missing the bound.
+            if lhs_pinned.is_some() && matches!(lhs_inner_ty.kind, ty::Param(_)) {
+                err.help(&format!(
+                    "if `{}` implements `{}<{}>`, consider using `Pin::{}()` to access the \
+                     pinned value",
+                    lhs_inner_ty,
+                    op_trait_path(op, is_assign),
+                    rhs_inner_ty,
+                    if is_assign == IsAssign::Yes { "as_mut" } else { "as_ref" },
+                ));
+            }
+            return false;
+        }
+        let source_map = self.tcx.sess.source_map();
+        // The pinned operands, and whether they are modified through the `Pin`.
+        let mut operands = vec![];
+        let pinned_operands = [(lhs_expr, lhs_pinned, true), (rhs_expr, rhs_pinned, false)];
+        for &(expr, pinned, is_lhs) in &pinned_operands {
+            let (pinned_ty, mutbl) = match pinned {
+                Some(pinned) => pinned,
+                None => continue,
+            };
+            let is_modified = is_lhs && is_assign == IsAssign::Yes;
+            if is_modified && mutbl == hir::Mutability::Not {
+                return false;
+            }
+            // Other than comparisons, operators take their operands by value.
+            if !is_modified
+                && !op.node.is_comparison()
+                && !self.infcx.type_is_copy_modulo_regions(self.param_env, pinned_ty, expr.span)
+            {
+                return false;
+            }
+            let snippet = match source_map.span_to_snippet(expr.span) {
+                Ok(snippet) => parenthesize(expr, snippet, PREC_POSTFIX),
+                Err(_) => return false,
+            };
+            operands.push((expr.span, snippet, pinned_ty, is_modified));
+        }
+        err.note(
+            "`Pin` intentionally restricts access to the value it points to, so operators can't \
+             be applied to it through the `Pin`",
+        );
+        let mut suggestions = vec![];
+        let mut modified_ty = None;
+        for (span, snippet, pinned_ty, is_modified) in operands {
+            if !is_modified {
+                suggestions.push((span, format!("*{}.as_ref().get_ref()", snippet)));
+                continue;
+            }
+            // Mutable access to the pinned value is only safe if it doesn't care about being
+            // moved.
+            let is_unpin = self.tcx.lang_items().unpin_trait().map_or(false, |unpin_trait| {
+                let ty = self.tcx.erase_regions(&pinned_ty);
+                !ty.has_infer_types()
+                    && self.tcx.type_implements_trait((
+                        unpin_trait,
+                        ty,
+                        ty::List::empty(),
+                        self.param_env,
+                    ))
+            });
+            if !is_unpin {
+                err.note(&format!(
+                    "`{}.as_mut().get_mut()` would give mutable access to the `{}`, but only if \
+                     it implements `Unpin`",
+                    snippet, pinned_ty,
+                ));
+                return true;
+            }
+            suggestions.push((span, format!("*{}.as_mut().get_mut()", snippet)));
+            modified_ty = Some(pinned_ty);
+        }
+        let msg = match (modified_ty, suggestions.len()) {
+            (Some(ty), 1) => {
+                format!("`{}` implements `Unpin`, so it can be modified with `get_mut`", ty)
+            }
+            (Some(ty), _) => format!(
+                "`{}` implements `Unpin`, so it can be modified with `get_mut`, and the other \
+                 pinned value read with `get_ref`",
+                ty,
+            ),
+            (None, 1) => "read the pinned value with `get_ref`".to_string(),
+            (None, _) => "read the pinned values with `get_ref`".to_string(),
+        };
+        if suggestions.len() == 1 {
+            let (span, sugg) = suggestions.remove(0);
+            err.span_suggestion_verbose(span, &msg, sugg, Applicability::MachineApplicable);
+        } else {
+            err.multipart_suggestion(&msg, suggestions, Applicability::MachineApplicable);
+        }
+        true
+    }
+
+    /// The name of a `Mutex`, `RwLock` or `RefCell`, its contents, and the methods giving shared
+    /// and mutable access to them.
+    fn lock_contents(
+        &self,
+        ty: Ty<'tcx>,
+    ) -> Option<(&'static str, Ty<'tcx>, &'static str, &'static str)> {
+        let (def, substs) = match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, substs) => (def, substs),
+            _ => return None,
+        };
+        let (name, read, write) = if self.tcx.is_diagnostic_item(sym::mutex_type, def.did) {
+            ("Mutex", "lock", "lock")
+        } else if self.tcx.is_diagnostic_item(sym::rwlock_type, def.did) {
+            ("RwLock", "read", "write")
+        } else if self.tcx.is_diagnostic_item(sym::refcell_type, def.did) {
+            ("RefCell", "borrow", "borrow_mut")
+        } else {
+            return None;
+        };
+        Some((name, substs.type_at(0), read, write))
+    }
+
+    /// Whether the operator is implemented for the contents of the `Mutex`, `RwLock` or `RefCell`
+    /// on the RHS, but not for the RHS itself.
+    fn is_op_implemented_for_rhs_lock_contents(
+        &self,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let rhs_ty = self.resolve_vars_if_possible(&rhs_ty);
+        let contents = match self.lock_contents(rhs_ty) {
+            Some((_, contents, _, _)) => contents,
+            None => return false,
+        };
+        !self.op_may_apply(lhs_ty, &[rhs_ty], Op::Binary(op, is_assign))
+            && self.op_may_apply(lhs_ty, &[contents], Op::Binary(op, is_assign))
+    }
+
+    /// Suggests locking the operands that are a `Mutex` or `RwLock`, or borrowing those that are a
+    /// `RefCell`, if the operator can be applied to their contents. Returns `true` if the
+    /// suggestion was given.
+    fn suggest_locking_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let (lhs_lock, rhs_lock) = (self.lock_contents(lhs_ty), self.lock_contents(rhs_ty));
+        if lhs_lock.is_none() && rhs_lock.is_none() {
+            return false;
+        }
+        if self
+            .lookup_op_method(
+                lhs_lock.map_or(lhs_ty, |(_, contents, _, _)| contents),
+                &[rhs_lock.map_or(rhs_ty, |(_, contents, _, _)| contents)],
+                Op::Binary(op, is_assign),
+            )
+            .is_err()
+        {
+            return false;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return false,
+        };
+        // Each operand, rewritten to access the contents if it is a lock, and the name of the lock,
+        // the method accessing its contents and whether they are modified.
+        let mut operands = vec![];
+        for &(expr, snippet, lock, is_lhs) in
+            &[(lhs_expr, &lhs, lhs_lock, true), (rhs_expr, &rhs, rhs_lock, false)]
+        {
+            let (name, contents, read, write) = match lock {
+                Some(lock) => lock,
+                None => {
+                    operands.push((expr.span, snippet.clone(), None));
+                    continue;
+                }
+            };
+            let is_modified = is_lhs && is_assign == IsAssign::Yes;
+            // Other than comparisons, operators take their operands by value.
+            if !is_modified
+                && !op.node.is_comparison()
+                && !self.infcx.type_is_copy_modulo_regions(self.param_env, contents, expr.span)
+            {
+                return false;
+            }
+            let method = if is_modified { write } else { read };
+            let receiver = parenthesize(expr, snippet.clone(), PREC_POSTFIX);
+            let unwrap = if name == "RefCell" { "" } else { ".unwrap()" };
+            let access = format!("*{}.{}(){}", receiver, method, unwrap);
+            operands.push((expr.span, access, Some((name, method, is_modified))));
+        }
+        let locks: Vec<_> = operands.iter().filter_map(|&(_, _, lock)| lock).collect();
+        if let Some(&(_, method, _)) = locks.iter().find(|&&(name, _, _)| name != "RefCell") {
+            err.note(&format!(
+                "`{}` returns an error if another thread panicked while holding the lock, which \
+                 `unwrap` turns into a panic",
+                method,
+            ));
+            err.note(&format!(
+                "the lock is held until the guard returned by `{}` is dropped",
+                method
+            ));
+        }
+        if let Some(&(name, method, true)) = locks.first() {
+            if name != "RefCell" {
+                // A temporary guard on the LHS is easy to miss, e.g. when the RHS locks the same
+                // `Mutex` again and deadlocks, so it gets a name.
+                err.help(&format!(
+                    "lock `{0}` into a local guard first, with \
+                     `let mut guard = {0}.{1}().unwrap();`, and then use `*guard {2}= {3}`",
+                    lhs,
+                    method,
+                    op.node.as_str(),
+                    operands[1].1,
+                ));
+                return true;
+            }
+        }
+        let msg = match &locks[..] {
+            [(name, method, _)] => {
+                format!("access the contents of the `{}` with `{}`", name, method)
+            }
+            [(_, lhs_method, _), (_, rhs_method, _)] if lhs_method == rhs_method => {
+                format!("access the contents of both operands with `{}`", lhs_method)
+            }
+            [(_, lhs_method, _), (_, rhs_method, _)] => format!(
+                "access the contents of the operands with `{}` and `{}`",
+                lhs_method, rhs_method,
+            ),
+            _ => return false,
+        };
+        let mut suggestions: Vec<_> = operands
+            .into_iter()
+            .filter(|(_, _, lock)| lock.is_some())
+            .map(|(span, access, _)| (span, access))
+            .collect();
+        if suggestions.len() == 1 {
+            let (span, sugg) = suggestions.remove(0);
+            err.span_suggestion_verbose(span, &msg, sugg, Applicability::MaybeIncorrect);
+        } else {
+            err.multipart_suggestion(&msg, suggestions, Applicability::MaybeIncorrect);
+        }
+        true
+    }
+
+    /// The type a `Cell` contains.
+    fn cell_contents(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, substs) if self.tcx.is_diagnostic_item(sym::cell_type, def.did) => {
+                Some(substs.type_at(0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether the operator is implemented for the contents of the `Cell` on the RHS, but not for
+    /// the RHS itself.
+    fn is_op_implemented_for_rhs_cell_contents(
+        &self,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        let rhs_ty = self.resolve_vars_if_possible(&rhs_ty);
+        let contents = match self.cell_contents(rhs_ty) {
+            Some(contents) => contents,
+            None => return false,
+        };
+        !self.op_may_apply(lhs_ty, &[rhs_ty], Op::Binary(op, is_assign))
+            && self.op_may_apply(lhs_ty, &[contents], Op::Binary(op, is_assign))
+    }
+
+    /// Suggests reading the operands that are a `Cell` with `get`, if the operator can be applied
+    /// to their contents. Returns `true` if the suggestion, or a note that `get` needs the contents
+    /// to be `Copy`, was given.
+    fn suggest_cell_get_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) -> bool {
+        // The value assigned to can't be a copy of the contents.
+        let lhs_cell = match is_assign {
+            IsAssign::Yes => None,
+            IsAssign::No => self.cell_contents(lhs_ty),
+        };
+        let rhs_cell = self.cell_contents(rhs_ty);
+        if (lhs_cell.is_none() && rhs_cell.is_none())
+            || self
+                .lookup_op_method(
+                    lhs_cell.unwrap_or(lhs_ty),
+                    &[rhs_cell.unwrap_or(rhs_ty)],
+                    Op::Binary(op, is_assign),
+                )
+                .is_err()
+        {
+            return false;
+        }
+        let cells: Vec<_> = vec![(lhs_expr, lhs_cell), (rhs_expr, rhs_cell)]
+            .into_iter()
+            .filter_map(|(expr, contents)| Some((expr, contents?)))
+            .collect();
+        self.suggest_cell_get(err, &cells)
+    }
+
+    /// Suggests reading the `Cell`s `cells` with `get`, or notes that their contents would have to
+    /// be `Copy` for that. Returns `true` if either was given.
+    fn suggest_cell_get(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        cells: &[(&'tcx hir::Expr<'tcx>, Ty<'tcx>)],
+    ) -> bool {
+        let source_map = self.tcx.sess.source_map();
+        let mut suggestions = vec![];
+        for &(expr, contents) in cells {
+            if !self.infcx.type_is_copy_modulo_regions(self.param_env, contents, expr.span) {
+                err.note(&format!(
+                    "`Cell::get` only reads values that are `Copy`, which `{0}` isn't; to borrow \
+                     the value instead, use a `RefCell<{0}>`",
+                    contents,
+                ));
+                return true;
+            }
+            let snippet = match source_map.span_to_snippet(expr.span) {
+                Ok(snippet) => snippet,
+                Err(_) => return false,
+            };
+            let receiver = parenthesize(expr, snippet, PREC_POSTFIX);
+            suggestions.push((expr.span, format!("{}.get()", receiver)));
+        }
+        if suggestions.len() == 1 {
+            let (span, sugg) = suggestions.remove(0);
+            err.span_suggestion_verbose(
+                span,
+                "read the value inside the `Cell` with `get`",
+                sugg,
+                Applicability::MachineApplicable,
+            );
+        } else {
+            err.multipart_suggestion(
+                "read the values inside the `Cell`s with `get`",
+                suggestions,
+                Applicability::MachineApplicable,
+            );
+        }
+        true
+    }
+
+    /// The type an `Option` contains.
+    fn option_contents(&self, ty: Ty<'tcx>) -> Option<Ty<'tcx>> {
+        match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, substs) if self.tcx.is_diagnostic_item(sym::option_type, def.did) => {
+                Some(substs.type_at(0))
+            }
+            _ => None,
+        }
+    }
+
+    /// Suggests applying an arithmetic operator to the contents of an `Option` operand, with `map`
+    /// if the result may stay optional, or after `unwrap_or` if the contents are what's expected.
+    /// Returns `true` if a suggestion was given.
+    fn suggest_option_arithmetic(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        expected: Expectation<'tcx>,
+    ) -> bool {
+        if !matches!(BinOpCategory::from(op), BinOpCategory::Math) {
+            return false;
+        }
+        let (option_expr, contents, is_lhs) =
+            match (self.option_contents(lhs_ty), self.option_contents(rhs_ty)) {
+                (Some(contents), None) => (lhs_expr, contents, true),
+                (None, Some(contents)) => (rhs_expr, contents, false),
+                _ => return false,
+            };
+        let (lhs_inner_ty, rhs_inner_ty) =
+            if is_lhs { (contents, rhs_ty) } else { (lhs_ty, contents) };
+        let output = match self.lookup_op_method(
+            lhs_inner_ty,
+            &[rhs_inner_ty],
+            Op::Binary(op, IsAssign::No),
+        ) {
+            Ok(method) => self.resolve_vars_if_possible(&method.sig.output()),
+            Err(()) => return false,
+        };
+        let source_map = self.tcx.sess.source_map();
+        let (option, other) = match (
+            source_map.span_to_snippet(option_expr.span),
+            source_map.span_to_snippet(if is_lhs { rhs_expr.span } else { lhs_expr.span }),
+        ) {
+            (Ok(option), Ok(other)) => (option, other),
+            _ => return false,
+        };
+        let receiver = parenthesize(option_expr, option.clone(), PREC_POSTFIX);
+
+        // The contents are expected if the expression has to be of the type that the operator
+        // gives for them, rather than an `Option`.
+        let expects_contents = expected.only_has_type(self).map_or(false, |expected| {
+            let expected = self.resolve_vars_if_possible(&expected);
+            !expected.is_ty_var()
+                && self.option_contents(expected).is_none()
+                && self.can_eq(self.param_env, expected, output).is_ok()
+        });
+        if expects_contents {
+            let default = match contents.kind {
+                ty::Int(_) | ty::Uint(_) | ty::Infer(ty::IntVar(_)) => "0",
+                ty::Float(_) | ty::Infer(ty::FloatVar(_)) => "0.0",
+                _ => return false,
+            };
+            err.span_suggestion_verbose(
+                option_expr.span,
+                &format!("if `None` should count as `{}`, use `unwrap_or`", default),
+                format!("{}.unwrap_or({})", receiver, default),
+                Applicability::MaybeIncorrect,
+            );
+        } else {
+            // The closure's parameter must not shadow a variable used by the other operand.
+            let binding = if other
+                .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+                .any(|word| word == "v")
+            {
+                "value"
+            } else {
+                "v"
+            };
+            let (lhs, rhs) = if is_lhs { (binding, &other[..]) } else { (&other[..], binding) };
+            err.span_suggestion_verbose(
+                expr.span,
+                &format!(
+                    "to apply `{}` to the value inside the `Option` and keep `None` as it is, \
+                     use `map`",
+                    op.node.as_str(),
+                ),
+                format!("{}.map(|{}| {} {} {})", receiver, binding, lhs, op.node.as_str(), rhs),
+                Applicability::MaybeIncorrect,
+            );
+        }
+
+        let returns_option = self.ret_coercion.as_ref().map_or(false, |ret_coercion| {
+            self.option_contents(ret_coercion.borrow().expected_ty()).is_some()
+        });
+        if returns_option {
+            err.note(&format!(
+                "alternatively, `{}?` returns `None` from the function early if `{}` is `None`",
+                receiver, option,
+            ));
+        }
+        true
+    }
+
+    /// Suggests comparing the value inside a `cmp::Reverse` operand with `.0`, when the other
+    /// operand has the type it wraps and the comparison failed to coerce the RHS.
+    fn suggest_unwrapping_reverse_operand(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !op.node.is_comparison() {
+            return;
+        }
+        let reverse_contents = |ty: Ty<'tcx>| match self.resolve_vars_if_possible(&ty).kind {
+            Adt(def, substs) if self.tcx.is_diagnostic_item(sym::reverse_type, def.did) => {
+                Some(substs.type_at(0))
+            }
+            _ => None,
+        };
+        let (reverse_expr, contents, other_ty) =
+            match (reverse_contents(lhs_ty), reverse_contents(rhs_ty)) {
+                (Some(contents), None) => (lhs_expr, contents, rhs_ty),
+                (None, Some(contents)) => (rhs_expr, contents, lhs_ty),
+                _ => return,
+            };
+        if self.can_eq(self.param_env, contents, other_ty).is_err() {
+            return;
+        }
+        let snippet = match self.tcx.sess.source_map().span_to_snippet(reverse_expr.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+        let receiver = parenthesize(reverse_expr, snippet, PREC_POSTFIX);
+        err.span_suggestion_verbose(
+            reverse_expr.span,
+            "compare the value inside the `Reverse` with `.0`",
+            format!("{}.0", receiver),
+            Applicability::MachineApplicable,
+        );
+        if !matches!(op.node, hir::BinOpKind::Eq | hir::BinOpKind::Ne) {
+            err.note(
+                "`Reverse` inverts the ordering of the value it wraps, so check that the \
+                 comparison still goes in the intended direction once it is unwrapped",
+            );
+        }
+    }
+
+    /// Explains that `None` equals neither `Some(true)` nor `Some(false)` when an `Option<bool>` is
+    /// compared with a `bool` literal, and suggests wrapping a literal LHS in `Some`, which the
+    /// coercion of the RHS already suggests for a literal RHS.
+    fn check_op_on_option_bool(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(op.node, hir::BinOpKind::Eq | hir::BinOpKind::Ne) {
+            return;
+        }
+        let is_bool_lit = |expr: &hir::Expr<'_>| match expr.kind {
+            hir::ExprKind::Lit(ref lit) => matches!(lit.node, ast::LitKind::Bool(_)),
+            _ => false,
+        };
+        let is_option_bool =
+            |ty: Ty<'tcx>| self.option_contents(ty).map_or(false, |contents| contents.is_bool());
+        let (option_expr, lit_expr, lit_is_lhs) = if is_option_bool(lhs_ty) && is_bool_lit(rhs_expr)
+        {
+            (lhs_expr, rhs_expr, false)
+        } else if is_bool_lit(lhs_expr) && is_option_bool(rhs_ty) {
+            (rhs_expr, lhs_expr, true)
+        } else {
+            return;
+        };
+        let source_map = self.tcx.sess.source_map();
+        let option = match source_map.span_to_snippet(option_expr.span) {
+            Ok(option) => format!("`{}`", option),
+            Err(_) => "the `Option`".to_string(),
+        };
+        err.note(&format!(
+            "{} can also be `None`, which is equal to neither `Some(true)` nor `Some(false)`",
+            option,
+        ));
+        if lit_is_lhs {
+            if let Ok(lit) = source_map.span_to_snippet(lit_expr.span) {
+                err.span_suggestion_verbose(
+                    lit_expr.span,
+                    "wrap the `bool` in `Some` to compare it with the `Option<bool>`",
+                    format!("Some({})", lit),
+                    Applicability::MachineApplicable,
+                );
+            }
+        }
+    }
+
+    /// Explains comparing a `bool` with an integer literal, as in `v.is_empty() == 0`, suggesting
+    /// to compare it with `true` or `false` instead, or to convert it to an integer with `as`.
+    fn check_cmp_with_bool_and_integer(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(op.node, hir::BinOpKind::Eq | hir::BinOpKind::Ne)
+            || !self.resolve_vars_if_possible(&lhs_ty).is_bool()
+        {
+            return;
+        }
+        // Chained comparisons like `a == b == 1` are already reported by the parser.
+        if let hir::ExprKind::Binary(lhs_op, ..) = lhs_expr.kind {
+            if lhs_op.node.is_comparison() {
+                return;
+            }
+        }
+        let (value, suffix) = match rhs_expr.kind {
+            hir::ExprKind::Lit(ref lit) => match lit.node {
+                ast::LitKind::Int(value, ast::LitIntType::Unsigned(uty)) => (value, uty.name_str()),
+                ast::LitKind::Int(value, ast::LitIntType::Signed(ity)) => (value, ity.name_str()),
+                ast::LitKind::Int(value, ast::LitIntType::Unsuffixed) => (value, "i32"),
+                _ => return,
+            },
+            _ => return,
+        };
+        err.note("`bool` isn't an integer type, and isn't converted to one implicitly");
+        if value <= 1 {
+            err.span_suggestion_verbose(
+                rhs_expr.span,
+                &format!("compare with `{}` instead, which converts to `{}`", value == 1, value),
+                (value == 1).to_string(),
+                Applicability::MachineApplicable,
+            );
+        }
+        if let Ok(lhs) = self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+            let lhs = parenthesize(lhs_expr, lhs, PREC_PREFIX);
+            err.span_suggestion_verbose(
+                lhs_expr.span,
+                "convert the `bool` to an integer with `as`, which is `1` for `true` and `0` \
+                 for `false`",
+                format!("{} as {}", lhs, suffix),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Suggests swapping the operands of a commutative operator that is implemented for them in
+    /// the other order, and notes that the order matters for the other operators. Returns whether
+    /// swapping was suggested.
+    fn suggest_swapped_operands(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) -> bool {
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        if lhs_ty.is_ty_var()
+            || rhs_ty.is_ty_var()
+            || self.can_eq(self.param_env, lhs_ty, rhs_ty).is_ok()
+            || self.lookup_op_method(rhs_ty, &[lhs_ty], Op::Binary(op, IsAssign::No)).is_err()
+        {
+            return false;
+        }
+        // `+` concatenates strings, which isn't commutative, and is explained elsewhere.
+        let is_string = |ty: Ty<'tcx>| match ty.peel_refs().kind {
+            Str => true,
+            Adt(def, _) => self.tcx.is_diagnostic_item(sym::string_type, def.did),
+            _ => false,
+        };
+        if is_string(lhs_ty) || is_string(rhs_ty) {
+            return false;
+        }
+        if !op_is_commutative(op.node) {
+            err.note(&format!(
+                "`{} {} {}` is implemented, but this operator is not commutative, so swapping \
+                 the operands would change the result",
+                rhs_ty,
+                op.node.as_str(),
+                lhs_ty
+            ));
+            return false;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return false,
+        };
+        err.multipart_suggestion(
+            &format!(
+                "`{} {} {}` is implemented, swap the operands",
+                rhs_ty,
+                op.node.as_str(),
+                lhs_ty
+            ),
+            vec![
+                (lhs_expr.span, parenthesize(rhs_expr, rhs, PREC_PREFIX)),
+                (rhs_expr.span, parenthesize(lhs_expr, lhs, PREC_PREFIX)),
+            ],
+            Applicability::MaybeIncorrect,
+        );
+        true
+    }
+
+    /// Suggests how to append a `Vec` to another one, since `Vec`s can't be added. `+=` and `+`
+    /// whose value is unused append in place with `extend`, otherwise a new vector is created.
+    fn suggest_vec_concat(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        if op.node != hir::BinOpKind::Add {
+            return;
+        }
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let is_vec = |ty: Ty<'tcx>| match ty.kind {
+            Adt(def, _) => self.tcx.is_diagnostic_item(sym::vec_type, def.did),
+            _ => false,
+        };
+        if !is_vec(lhs_ty)
+            || !is_vec(rhs_ty)
+            || self.can_eq(self.param_env, lhs_ty, rhs_ty).is_err()
+        {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        let receiver = parenthesize(lhs_expr, lhs.clone(), PREC_POSTFIX);
+        if is_assign == IsAssign::Yes || self.is_unused_value(expr) {
+            err.span_suggestion_verbose(
+                expr.span,
+                &format!("to append the elements of `{}` to `{}`, use `extend`", rhs, lhs),
+                format!("{}.extend({})", receiver, rhs),
+                Applicability::MaybeIncorrect,
+            );
+        } else {
+            err.span_suggestion_verbose(
+                expr.span,
+                "to create a new vector with the elements of both, use `concat`",
+                format!("[{}, {}].concat()", lhs, rhs),
+                Applicability::MaybeIncorrect,
+            );
+            err.span_suggestion_verbose(
+                expr.span,
+                "or, to move the elements instead of cloning them, chain them",
+                format!("{}.into_iter().chain({}).collect::<Vec<_>>()", receiver, rhs),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Suggests `concat` when two slices, or references to arrays, with the same element type are
+    /// added. `&str` operands never get here, they are handled by `check_str_addition`.
+    fn suggest_slice_concat(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if op.node != hir::BinOpKind::Add {
+            return;
+        }
+        // The element type of a `&[T]` or `&[T; N]`, and whether it is the latter.
+        let elements = |ty: Ty<'tcx>| match self.resolve_vars_if_possible(&ty).kind {
+            Ref(_, pointee, _) => match pointee.kind {
+                ty::Slice(elem) => Some((elem, false)),
+                Array(elem, _) => Some((elem, true)),
+                _ => None,
+            },
+            _ => None,
+        };
+        let (elem, lhs_is_array, rhs_is_array) = match (elements(lhs_ty), elements(rhs_ty)) {
+            (Some((lhs_elem, lhs_is_array)), Some((rhs_elem, rhs_is_array)))
+                if self.can_eq(self.param_env, lhs_elem, rhs_elem).is_ok() =>
+            {
+                (lhs_elem, lhs_is_array, rhs_is_array)
+            }
+            _ => return,
+        };
+        let source_map = self.tcx.sess.source_map();
+        // `concat` needs both elements of the array it is called on to be slices.
+        let as_slice = |expr: &hir::Expr<'_>, is_array: bool| {
+            let (expr, snippet) = match expr.kind {
+                hir::ExprKind::AddrOf(hir::BorrowKind::Ref, _, inner) if is_array => {
+                    (inner, source_map.span_to_snippet(inner.span).ok()?)
+                }
+                _ => (expr, source_map.span_to_snippet(expr.span).ok()?),
+            };
+            Some(if is_array {
+                format!("&{}[..]", parenthesize(expr, snippet, PREC_POSTFIX))
+            } else {
+                snippet
+            })
+        };
+        let (lhs, rhs) = match (as_slice(lhs_expr, lhs_is_array), as_slice(rhs_expr, rhs_is_array))
+        {
+            (Some(lhs), Some(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        err.note(&format!(
+            "`concat` copies the elements of both slices into a newly allocated `Vec<{}>`",
+            self.resolve_vars_if_possible(&elem)
+        ));
+        err.span_suggestion_verbose(
+            expr.span,
+            "to create a new vector with the elements of both, use `concat`",
+            format!("[{}, {}].concat()", lhs, rhs),
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    /// Suggests how to merge a `HashMap` or `BTreeMap` into another one, since maps can't be
+    /// added. `+=` and `+` whose value is unused become `extend`, otherwise a new map is collected.
+    fn suggest_map_extend(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+    ) {
+        if op.node != hir::BinOpKind::Add {
+            return;
+        }
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let map = |ty: Ty<'tcx>| match ty.kind {
+            Adt(def, substs)
+                if self.tcx.is_diagnostic_item(sym::hashmap_type, def.did)
+                    || self.tcx.is_diagnostic_item(sym::btreemap_type, def.did) =>
+            {
+                Some((def.did, substs.type_at(0), substs.type_at(1)))
+            }
+            _ => None,
+        };
+        let map_def_id = match (map(lhs_ty), map(rhs_ty)) {
+            (Some((lhs_def_id, lhs_key, lhs_value)), Some((rhs_def_id, rhs_key, rhs_value)))
+                if lhs_def_id == rhs_def_id
+                    && self.can_eq(self.param_env, lhs_key, rhs_key).is_ok()
+                    && self.can_eq(self.param_env, lhs_value, rhs_value).is_ok() =>
+            {
+                lhs_def_id
+            }
+            _ => return,
+        };
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        let receiver = parenthesize(lhs_expr, lhs.clone(), PREC_POSTFIX);
+        err.note(&format!(
+            "for keys present in both maps, the values from `{}` replace those from `{}`",
+            rhs, lhs
+        ));
+        if is_assign == IsAssign::Yes || self.is_unused_value(expr) {
+            err.span_suggestion_verbose(
+                expr.span,
+                &format!("to insert the entries of `{}` into `{}`, use `extend`", rhs, lhs),
+                format!("{}.extend({})", receiver, rhs),
+                Applicability::MaybeIncorrect,
+            );
+        } else {
+            err.span_suggestion_verbose(
+                expr.span,
+                "to create a new map with the entries of both, chain them",
+                format!(
+                    "{}.into_iter().chain({}).collect::<{}<_, _>>()",
+                    receiver,
+                    rhs,
+                    self.tcx.item_name(map_def_id)
+                ),
+                Applicability::MaybeIncorrect,
+            );
+        }
+    }
+
+    /// Whether `expr` is the expression of a statement, so that its value is discarded.
+    fn is_unused_value(&self, expr: &hir::Expr<'_>) -> bool {
+        match self.tcx.hir().get(self.tcx.hir().get_parent_node(expr.hir_id)) {
+            hir::Node::Stmt(stmt) => matches!(stmt.kind, hir::StmtKind::Semi(_)),
+            _ => false,
+        }
+    }
+
+    /// Suggests chaining two iterators that were added together.
+    fn suggest_iterator_chain(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if op.node != hir::BinOpKind::Add {
+            return;
+        }
+        let iterator_trait = match self.tcx.get_diagnostic_item(sym::Iterator) {
+            Some(iterator_trait) => iterator_trait,
+            None => return,
+        };
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let is_iterator = |ty: Ty<'tcx>| {
+            // Inference variables can't be checked for implementing `Iterator`.
+            !ty.has_infer_types()
+                && self.tcx.type_implements_trait((
+                    iterator_trait,
+                    self.tcx.erase_regions(&ty),
+                    ty::List::empty(),
+                    self.param_env,
+                ))
+        };
+        if !is_iterator(lhs_ty) || !is_iterator(rhs_ty) {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        let receiver = parenthesize(lhs_expr, lhs, PREC_POSTFIX);
+        let is_range = |ty: Ty<'tcx>| match ty.kind {
+            ty::Adt(def, _) => self.tcx.is_diagnostic_item(sym::Range, def.did),
+            _ => false,
+        };
+        if is_range(lhs_ty) && is_range(rhs_ty) {
+            err.note("ranges are iterators themselves, so they can be chained directly");
+        }
+        err.span_suggestion_verbose(
+            expr.span,
+            "to iterate over the elements of both, chain the iterators",
+            format!("{}.chain({})", receiver, rhs),
+            Applicability::MaybeIncorrect,
+        );
+    }
+
+    /// Explains that arithmetic operators don't apply to arrays and slices element by element, and
+    /// shows how to do that with iterators instead.
+    fn note_array_elementwise_op(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(BinOpCategory::from(op), BinOpCategory::Math) {
+            return;
+        }
+        // The element type and the length, if known, of an array or slice operand.
+        let elements = |ty: Ty<'tcx>| match self.resolve_vars_if_possible(&ty).peel_refs().kind {
+            Array(elem, len) => Some((elem, len.try_eval_usize(self.tcx, self.param_env))),
+            ty::Slice(elem) => Some((elem, None)),
+            _ => None,
+        };
+        let (lhs_elem, lhs_len, rhs_elem) = match (elements(lhs_ty), elements(rhs_ty)) {
+            (Some((lhs_elem, lhs_len)), Some((rhs_elem, _)))
+                if lhs_elem.is_numeric()
+                    && rhs_elem.is_numeric()
+                    && self.can_eq(self.param_env, lhs_elem, rhs_elem).is_ok() =>
+            {
+                (lhs_elem, lhs_len, rhs_elem)
+            }
+            _ => return,
+        };
+        err.note("there is no element-wise arithmetic on arrays or slices");
+        let source_map = self.tcx.sess.source_map();
+        if let (Ok(lhs), Ok(rhs)) =
+            (source_map.span_to_snippet(lhs_expr.span), source_map.span_to_snippet(rhs_expr.span))
+        {
+            err.help(&format!(
+                "to apply `{op}` to each pair of elements, zip them: \
+                 `{}.iter().zip({}.iter()).map(|(x, y)| x {op} y).collect::<Vec<_>>()`",
+                parenthesize(lhs_expr, lhs, PREC_POSTFIX),
+                parenthesize(rhs_expr, rhs, PREC_POSTFIX),
+                op = op.node.as_str(),
+            ));
+        }
+        let elem_bits = match (&lhs_elem.kind, &rhs_elem.kind) {
+            (ty::Int(int_ty), _) | (_, ty::Int(int_ty)) => int_ty.bit_width(),
+            (Uint(uint_ty), _) | (_, Uint(uint_ty)) => uint_ty.bit_width(),
+            (ty::Float(float_ty), _) | (_, ty::Float(float_ty)) => Some(float_ty.bit_width()),
+            // Integer and float literals fall back to `i32` and `f64`.
+            (ty::Infer(ty::FloatVar(_)), _) => Some(64),
+            _ => Some(32),
+        }
+        .unwrap_or_else(|| self.tcx.data_layout.pointer_size.bits());
+        if let Some(len) = lhs_len {
+            let bits = len.checked_mul(elem_bits);
+            if len >= 2 && len.is_power_of_two() && matches!(bits, Some(64 | 128 | 256 | 512)) {
+                err.note(
+                    "SIMD types implement element-wise arithmetic, and may be a better fit for \
+                     vectors of numbers of this size",
+                );
+            }
+        }
+    }
+
+    /// Suggests borrowing both operands of a set operation on two `HashSet`s or `BTreeSet`s, since
+    /// the operators are only implemented for references to sets.
+    fn suggest_borrowed_set_op(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        let method = match op.node {
+            hir::BinOpKind::BitOr => "union",
+            hir::BinOpKind::BitAnd => "intersection",
+            hir::BinOpKind::BitXor => "symmetric_difference",
+            hir::BinOpKind::Sub => "difference",
+            _ => return,
+        };
+        let (lhs_ty, rhs_ty) =
+            (self.resolve_vars_if_possible(&lhs_ty), self.resolve_vars_if_possible(&rhs_ty));
+        let is_set = |ty: Ty<'tcx>| match ty.kind {
+            Adt(def, _) => {
+                self.tcx.is_diagnostic_item(sym::hashset_type, def.did)
+                    || self.tcx.is_diagnostic_item(sym::btreeset_type, def.did)
+            }
+            _ => false,
+        };
+        if !is_set(lhs_ty)
+            || !is_set(rhs_ty)
+            || self.can_eq(self.param_env, lhs_ty, rhs_ty).is_err()
+        {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        let borrow = |expr: &hir::Expr<'_>, snippet: &str| {
+            if expr.precedence().order() < PREC_PREFIX as i8 {
+                (expr.span, format!("&({})", snippet))
+            } else {
+                (expr.span.shrink_to_lo(), "&".to_string())
+            }
+        };
+        err.multipart_suggestion(
+            &format!(
+                "`{}` is only implemented for references to sets, consider borrowing both operands",
+                op.node.as_str(),
+            ),
+            vec![borrow(lhs_expr, &lhs), borrow(rhs_expr, &rhs)],
+            Applicability::MachineApplicable,
+        );
+        err.note("this creates a new set, leaving the borrowed sets unchanged");
+        let receiver = parenthesize(lhs_expr, lhs, PREC_POSTFIX);
+        let rhs = format!("&{}", parenthesize(rhs_expr, rhs, PREC_PREFIX));
+        err.help(&format!(
+            "to iterate over the elements without creating a new set, use `{}.{}({})`",
+            receiver, method, rhs,
+        ));
+    }
+
+    /// Defers an operator error that can be fixed by restricting the type parameter `p` with
+    /// `constraint`, so that `report_deferred_op_bound_errors` can suggest the bounds needed by all
+    /// such errors in the body together. The body counts as erroneous from here on.
+    fn defer_op_bound_error(&self, err: DiagnosticBuilder<'_>, p: ty::ParamTy, constraint: String) {
+        if let Some((diagnostic, _)) = err.into_diagnostic() {
+            self.set_tainted_by_errors();
+            self.deferred_op_bound_errors.borrow_mut().push((diagnostic, p, constraint));
+        }
+    }
+
+    /// Notes when the default of the type parameter `p` supports the operator that `p` is missing,
+    /// as the code was then probably written with only the default in mind.
+    fn check_op_on_type_param_with_default(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        p: ty::ParamTy,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+        is_assign: IsAssign,
+        missing_trait: &str,
+    ) {
+        let tcx = self.tcx;
+        let def_id = tcx.hir().body_owner_def_id(hir::BodyId { hir_id: self.body_id });
+        let default_ty = match type_param_default(tcx, def_id.to_def_id(), p) {
+            Some(default_ty) if !default_ty.needs_subst() => default_ty,
+            _ => return,
+        };
+        // `T + T` has to work with the default on both sides.
+        let param_ty = p.to_ty(tcx);
+        let rhs_ty = if rhs_ty == param_ty { default_ty } else { rhs_ty };
+        if self.lookup_op_method(default_ty, &[rhs_ty], Op::Binary(op, is_assign)).is_ok() {
+            err.note(&format!(
+                "the default type `{}` supports this operator; you may need to add a bound \
+                 `{}: {}` to use other types",
+                default_ty, p, missing_trait,
+            ));
+        }
+    }
+
+    /// Emits the errors deferred by `defer_op_bound_error`, once the whole body has been checked.
+    /// If they need more than one bound, the first error gets a single suggestion adding all of
+    /// them, instead of each error suggesting a bound of its own.
+    pub fn report_deferred_op_bound_errors(&self) {
+        let deferred = mem::take(&mut *self.deferred_op_bound_errors.borrow_mut());
+        let mut params: Vec<(ty::ParamTy, String)> = vec![];
+        for (_, p, constraint) in &deferred {
+            if !params.iter().any(|(other, c)| other == p && c == constraint) {
+                params.push((*p, constraint.clone()));
+            }
+        }
+        let combine = params.len() > 1;
+
+        let handler = self.tcx.sess.diagnostic();
+        for (i, (mut diagnostic, p, constraint)) in deferred.into_iter().enumerate() {
+            if !combine {
+                suggest_constraining_params(
+                    self.tcx,
+                    self.body_id,
+                    &mut diagnostic,
+                    &[(p, constraint)],
+                );
+            } else if i == 0 {
+                suggest_constraining_params(self.tcx, self.body_id, &mut diagnostic, &params);
+            } else {
+                diagnostic.note(&format!("the suggestion above also restricts `{}`", p));
+            }
+            handler.emit_diagnostic(&diagnostic);
+        }
+    }
+
+    /// Notes the value of an arithmetic or bitwise operation on two integer literals, such as
+    /// `3u32 + 4u32`, whose type didn't match the expected one.
+    pub fn note_const_binop_value(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &hir::Expr<'_>,
+        expr_ty: Ty<'tcx>,
+    ) {
+        if let hir::ExprKind::Binary(op, ref lhs_expr, ref rhs_expr) = expr.kind {
+            let expr_ty = self.resolve_vars_if_possible(&expr_ty);
+            if let Some(value) = const_op_eval(self.tcx, op, lhs_expr, rhs_expr, expr_ty) {
+                err.note(&format!("the value of this expression is `{}{}`", value, expr_ty));
+            }
+        }
+    }
+
+    /// Points out that the type of a builtin shift, such as `x << n`, is the type of its left
+    /// operand, when another integer type was expected.
+    pub fn note_shift_result_type(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &hir::Expr<'_>,
+        expected: Ty<'tcx>,
+    ) {
+        if let hir::ExprKind::Binary(op, ref lhs_expr, _) = expr.kind {
+            let lhs_ty = self.resolve_vars_if_possible(&self.node_ty(lhs_expr.hir_id));
+            if matches!(BinOpCategory::from(op), BinOpCategory::Shift)
+                && deref_ty_if_possible(lhs_ty).is_integral()
+                && self.resolve_vars_if_possible(&expected).is_integral()
+            {
+                err.span_label(op.span, "the result type of a shift follows the left operand");
+            }
+        }
+    }
+
+    /// Reports comparing or doing arithmetic on a SIMD vector and an operand of a different
+    /// shape, i.e. a vector with another number of lanes or type of elements or a scalar, stating
+    /// the lanes of both operands. Returns whether an error was reported.
+    fn report_simd_operand_mismatch(
+        &self,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        expected_rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) -> bool {
+        let is_comparison = match BinOpCategory::from(op) {
+            BinOpCategory::Comparison => true,
+            BinOpCategory::Math | BinOpCategory::Bitwise => false,
+            _ => return false,
+        };
+        let rhs_ty = self.resolve_vars_with_obligations(rhs_ty);
+        let expected_rhs_ty = self.resolve_vars_with_obligations(expected_rhs_ty);
+        // Only the operator's implementation knows which operands it accepts, so this can only
+        // be a mismatch once the implementation has been selected.
+        if rhs_ty.is_ty_var() || expected_rhs_ty.is_ty_var() {
+            return false;
+        }
+        if self.can_coerce(rhs_ty, expected_rhs_ty) {
+            return false;
+        }
+        let (lhs_lanes, rhs_lanes) = (simd_lanes(self.tcx, lhs_ty), simd_lanes(self.tcx, rhs_ty));
+        let (vector_ty, (len, elem), other_ty, other_lanes, other_side) =
+            match (lhs_lanes, rhs_lanes) {
+                (Some(lanes), _) => (lhs_ty, lanes, rhs_ty, rhs_lanes, "right"),
+                (None, Some(lanes)) => (rhs_ty, lanes, lhs_ty, lhs_lanes, "left"),
+                (None, None) => return false,
+            };
+        if other_lanes.is_none() && !other_ty.is_scalar() {
+            return false;
+        }
+        let other_elem = other_lanes.map_or(other_ty, |(_, elem)| elem);
+        let same_elem = self.can_eq(self.param_env, elem, other_elem).is_ok();
+        // Distinct vector types of the same shape are left to the generic type mismatch.
+        let same_len = other_lanes.map_or(false, |(other_len, _)| other_len == len);
+        if same_len && same_elem {
+            return false;
+        }
+
+        let describe = |ty: Ty<'tcx>| match simd_lanes(self.tcx, ty) {
+            Some((len, elem)) => format!("`{}` ({} lanes of {})", ty, len, elem),
+            None => format!("`{}`", ty),
+        };
+        let message = if is_comparison {
+            format!("cannot compare {} with {}", describe(lhs_ty), describe(rhs_ty))
+        } else {
+            format!(
+                "cannot apply `{}` to {} and {}",
+                op.node.as_str(),
+                describe(lhs_ty),
+                describe(rhs_ty)
+            )
+        };
+        let mut err = struct_span_err!(self.tcx.sess, rhs_expr.span, E0308, "{}", message);
+        err.span_label(
+            rhs_expr.span,
+            format!("expected `{}`, found `{}`", expected_rhs_ty, rhs_ty),
+        );
+        match other_lanes {
+            // Both operands are vectors, so the left-hand side is `vector_ty`.
+            Some((other_len, _)) => {
+                if !same_len {
+                    err.note(&format!("lane counts differ: {} vs {}", len, other_len));
+                }
+                if !same_elem {
+                    err.note(&format!("element types differ: `{}` vs `{}`", elem, other_elem));
+                }
+                if same_elem {
+                    err.help(&format!(
+                        "both operands have `{}` lanes; consider shuffling the {}-hand side into \
+                         {} lanes first",
+                        elem, other_side, len
+                    ));
+                } else if same_len {
+                    err.help(&format!(
+                        "consider converting the {}-hand side to `{}` lanes with the `simd_cast` \
+                         intrinsic first",
+                        other_side, elem
+                    ));
+                } else {
+                    err.note(
+                        "the `simd_shuffle` and `simd_cast` intrinsics convert the lane count and \
+                         the element type of a vector",
+                    );
+                }
+            }
+            None if same_elem => {
+                err.help(&format!(
+                    "consider extending the {}-hand side to all {} lanes of a `{}` first",
+                    other_side, len, vector_ty
+                ));
+            }
+            None => {}
+        }
+        err.emit();
+        true
+    }
+
+    /// Lints arithmetic where one of the operands is a zero-sized type. Such an operation can only
+    /// be an overloaded one, and it cannot have any effect on the operands' data.
+    fn check_op_on_zst(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(BinOpCategory::from(op), BinOpCategory::Math) || expr.span.from_expansion() {
+            return;
+        }
+        let zst_operands: Vec<_> = [(lhs_expr.span, lhs_ty), (rhs_expr.span, rhs_ty)]
+            .iter()
+            .filter(|(_, ty)| self.is_known_zst(*ty))
+            .copied()
+            .collect();
+        if zst_operands.is_empty() {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(lint::builtin::OP_ON_ZST, expr.hir_id, op.span, |lint| {
+            let mut err = lint.build(&format!(
+                "binary operation `{}` applied to a zero-sized type",
+                op.node.as_str()
+            ));
+            for (span, ty) in zst_operands {
+                err.span_label(span, format!("`{}` is zero-sized", ty));
+            }
+            err.note(
+                "arithmetic on zero-sized types is a no-op at the data level, though it may \
+                 have side effects through the operator's implementation",
+            );
+            err.emit();
+        });
+    }
+
+    /// Lints on `==` and `!=` between function pointers, as the same function may have several
+    /// addresses and different functions may share the same one.
+    fn check_fn_ptr_comparison(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(op.node, hir::BinOpKind::Eq | hir::BinOpKind::Ne)
+            || expr.span.in_derive_expansion()
+        {
+            return;
+        }
+        let is_fn_ptr = |ty: Ty<'tcx>| matches!(deref_ty_if_possible(ty).kind, ty::FnPtr(_));
+        if !is_fn_ptr(lhs_ty) || !is_fn_ptr(rhs_ty) {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::UNPREDICTABLE_FUNCTION_POINTER_COMPARISONS,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                lint.build(
+                    "function pointer comparisons do not produce meaningful results since their \
+                     addresses are not guaranteed to be unique",
+                )
+                .note("the address of the same function can vary between different codegen units")
+                .note(
+                    "furthermore, different functions could have the same address after being \
+                     merged together",
+                )
+                .help(
+                    "consider comparing a dedicated enum or ID identifying the functions instead, \
+                     or using `std::ptr::eq` on data pointers",
+                )
+                .emit();
+            },
+        );
+    }
+
+    /// Lints on `x ^ 2` and the like, which is XOR but was probably meant to be exponentiation.
+    fn check_xor_used_as_pow(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if op.node != hir::BinOpKind::BitXor
+            || expr.span.from_expansion()
+            || !self.resolve_vars_if_possible(&lhs_ty).is_integral()
+        {
+            return;
+        }
+        match int_lit_value(rhs_expr) {
+            Some(2..=10) => {}
+            _ => return,
+        }
+        // Leave alone what looks like bit twiddling: single-bit constants, and the results of
+        // other bitwise operations.
+        match lhs_expr.kind {
+            _ if int_lit_value(lhs_expr).map_or(false, u128::is_power_of_two) => return,
+            hir::ExprKind::Binary(lhs_op, ..)
+                if matches!(
+                    BinOpCategory::from(lhs_op),
+                    BinOpCategory::Bitwise | BinOpCategory::Shift
+                ) =>
+            {
+                return;
+            }
+            _ => {}
+        }
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::XOR_USED_AS_POW,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                let mut err = lint.build("`^` is bitwise XOR, not exponentiation");
+                let source_map = self.tcx.sess.source_map();
+                if let (Ok(lhs), Ok(rhs)) = (
+                    source_map.span_to_snippet(lhs_expr.span),
+                    source_map.span_to_snippet(rhs_expr.span),
+                ) {
+                    // The span of a parenthesized expression includes the parentheses.
+                    let lhs = if lhs.starts_with('(') && lhs.ends_with(')') {
+                        lhs
+                    } else {
+                        parenthesize(lhs_expr, lhs, PREC_POSTFIX)
+                    };
+                    err.span_suggestion(
+                        expr.span,
+                        "to raise to a power, use `pow`",
+                        format!("{}.pow({})", lhs, rhs),
+                        Applicability::MaybeIncorrect,
+                    );
+                }
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints on builtin comparisons of a place with itself. They are constant, except for floats,
+    /// where they test whether the value is NaN and are clearer written with `is_nan`.
+    fn check_self_comparison(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(BinOpCategory::from(op), BinOpCategory::Comparison)
+            || expr.span.from_expansion()
+            || lhs_expr.span.from_expansion()
+            || rhs_expr.span.from_expansion()
+            || !is_same_place(lhs_expr, rhs_expr)
+        {
+            return;
+        }
+        let is_float =
+            deref_ty_if_possible(self.resolve_vars_if_possible(&lhs_ty)).is_floating_point();
+        let (always, is_nan_check) = match op.node {
+            hir::BinOpKind::Eq | hir::BinOpKind::Le | hir::BinOpKind::Ge => (true, is_float),
+            hir::BinOpKind::Ne => (false, is_float),
+            _ => (false, false),
+        };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::SELF_COMPARISONS,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                if !is_nan_check {
+                    lint.build(&format!(
+                        "comparison of a value with itself is always `{}`",
+                        always
+                    ))
+                    .emit();
+                    return;
+                }
+                let mut err = lint.build("comparison of a float with itself only checks for NaN");
+                if let Ok(operand) = self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+                    let operand = parenthesize(lhs_expr, operand, PREC_POSTFIX);
+                    // `x == x` holds for everything but NaN, while `x != x` only holds for NaN.
+                    let negation = if always { "!" } else { "" };
+                    err.span_suggestion(
+                        expr.span,
+                        "use `is_nan` instead",
+                        format!("{}{}.is_nan()", negation, operand),
+                        Applicability::MachineApplicable,
+                    );
+                }
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints on `&` and `|` between booleans when the right-hand side makes calls, which are made
+    /// even when the left-hand side already decides the result.
+    fn check_non_short_circuit_bool_op(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        let (short_circuit, skipped_when) = match op.node {
+            hir::BinOpKind::BitAnd => ("&&", "false"),
+            hir::BinOpKind::BitOr => ("||", "true"),
+            _ => return,
+        };
+        let is_bool =
+            |ty: Ty<'tcx>| deref_ty_if_possible(self.resolve_vars_if_possible(&ty)).is_bool();
+        if expr.span.from_expansion() || !is_bool(lhs_ty) || !is_bool(rhs_ty) {
+            return;
+        }
+        let mut finder = CallFinder { found: false };
+        finder.visit_expr(rhs_expr);
+        if !finder.found {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::NON_SHORT_CIRCUIT_BOOL_OPS,
+            expr.hir_id,
+            op.span,
+            |lint| {
+                lint.build(&format!(
+                    "`{}` evaluates its right-hand side even when the left-hand side is `{}`",
+                    op.node.as_str(),
+                    skipped_when
+                ))
+                .span_suggestion(
+                    op.span,
+                    &format!("use `{}` to skip the right-hand side", short_circuit),
+                    short_circuit.to_string(),
+                    Applicability::MaybeIncorrect,
+                )
+                .note(&format!(
+                    "`{}` does not evaluate its right-hand side when the left-hand side is `{}`, \
+                     so the calls in it are no longer made in that case",
+                    short_circuit, skipped_when
+                ))
+                .emit();
+            },
+        );
+    }
+
+    /// Checks an operand of `&&` or `||`, which has to be coercible to `bool`, and explains how to
+    /// test an operand of another type that C-like languages would accept.
+    fn check_short_circuit_operand(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        operand: &'tcx hir::Expr<'tcx>,
+    ) {
+        let bool_ty = self.tcx.types.bool;
+        let ty = self.check_expr_with_hint(operand, bool_ty);
+        let (_, err) = self.demand_coerce_diag(operand, ty, bool_ty, AllowTwoPhase::No);
+        if let Some(mut err) = err {
+            let operand = operand.peel_drop_temps();
+            let ty = self.resolve_vars_if_possible(&ty);
+            if !self.suggest_checking_variant_of_operand(&mut err, expr, operand, ty) {
+                self.suggest_derefs_of_bool_operand(&mut err, operand, ty);
+                self.suggest_comparing_operand_with_zero(&mut err, operand, ty);
+            }
+            err.emit();
+        }
+    }
+
+    /// Suggests testing an `Option` or `Result` operand of `&&` or `||` with `is_some()` or
+    /// `is_ok()`. Returns `true` if the operand is one of them.
+    fn suggest_checking_variant_of_operand(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        operand: &'tcx hir::Expr<'tcx>,
+        ty: Ty<'tcx>,
+    ) -> bool {
+        let (method, variant, what) = match ty.kind {
+            Adt(def, _) if self.tcx.is_diagnostic_item(sym::option_type, def.did) => {
+                ("is_some", "Some", "`Option` is `Some`")
+            }
+            Adt(def, _) if self.tcx.is_diagnostic_item(sym::result_type, def.did) => {
+                ("is_ok", "Ok", "`Result` is `Ok`")
+            }
+            _ => return false,
+        };
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(operand.span) {
+            let snippet = parenthesize(operand, snippet, PREC_POSTFIX);
+            err.span_suggestion_verbose(
+                operand.span,
+                &format!("use `{}()` to check whether the {}", method, what),
+                format!("{}.{}()", snippet, method),
+                Applicability::MachineApplicable,
+            );
+            if self.is_used_in_if_body(expr, operand) {
+                let binding = match operand.kind {
+                    hir::ExprKind::Path(hir::QPath::Resolved(None, path)) => {
+                        let name = path.segments[0].ident.as_str();
+                        ["maybe_", "opt_"]
+                            .iter()
+                            .find_map(|prefix| name.strip_prefix(prefix))
+                            .filter(|name| !name.is_empty())
+                            .unwrap_or("value")
+                            .to_string()
+                    }
+                    _ => "value".to_string(),
+                };
+                err.note(&format!(
+                    "to use the value inside `{}` in the body of the `if`, bind it with \
+                     `if let {}({}) = {}` and check the rest of the condition inside",
+                    snippet, variant, binding, snippet,
+                ));
+            }
+        }
+        true
+    }
+
+    /// Suggests dereferencing an operand of `&&` or `||` that is a `bool` behind several
+    /// references, like the `&&bool` items of a `&[&bool]`. The coercion error already suggests
+    /// dereferencing a single reference.
+    fn suggest_derefs_of_bool_operand(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        operand: &'tcx hir::Expr<'tcx>,
+        ty: Ty<'tcx>,
+    ) {
+        let mut referent = ty;
+        let mut derefs = 0;
+        while let Ref(_, inner, _) = referent.kind {
+            referent = inner;
+            derefs += 1;
+        }
+        if !referent.is_bool() || derefs < 2 || operand.span.from_expansion() {
+            return;
+        }
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(operand.span) {
+            let snippet = parenthesize(operand, snippet, PREC_PREFIX);
+            err.span_suggestion(
+                operand.span,
+                "consider dereferencing the borrow",
+                format!("{}{}", "*".repeat(derefs), snippet),
+                Applicability::MachineApplicable,
+            );
+        }
+    }
+
+    /// Suggests comparing an integer or float operand of `&&` or `||` with zero, or testing a raw
+    /// pointer operand with `is_null()`, as in `if count && flag`.
+    fn suggest_comparing_operand_with_zero(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        operand: &'tcx hir::Expr<'tcx>,
+        ty: Ty<'tcx>,
+    ) {
+        // Comparing a literal like the `1` in `1 && 2` with zero wouldn't make it any more useful.
+        let literal = match operand.kind {
+            hir::ExprKind::Unary(hir::UnOp::UnNeg, inner) => inner,
+            _ => operand,
+        };
+        if let hir::ExprKind::Lit(_) = literal.kind {
+            return;
+        }
+        let snippet = match self.tcx.sess.source_map().span_to_snippet(operand.span) {
+            Ok(snippet) => snippet,
+            Err(_) => return,
+        };
+        let comparison = AssocOp::NotEqual.precedence() as i8 + 1;
+        if ty.is_integral() {
+            err.note("integers aren't implicitly converted to `bool` in Rust");
+            err.span_suggestion_verbose(
+                operand.span,
+                "compare with `0` to check whether it's nonzero",
+                format!("{} != 0", parenthesize(operand, snippet, comparison)),
+                Applicability::MachineApplicable,
+            );
+        } else if ty.is_floating_point() {
+            err.note("floats aren't implicitly converted to `bool` in Rust");
+            err.span_suggestion_verbose(
+                operand.span,
+                "compare with `0.0` to check whether it's nonzero, which is also true for `NaN`",
+                format!("{} != 0.0", parenthesize(operand, snippet, comparison)),
+                Applicability::MaybeIncorrect,
+            );
+        } else if ty.is_unsafe_ptr() {
+            err.note("raw pointers aren't implicitly converted to `bool` in Rust");
+            err.span_suggestion_verbose(
+                operand.span,
+                "use `is_null()` to check whether the pointer is non-null",
+                format!("!{}.is_null()", parenthesize(operand, snippet, PREC_POSTFIX)),
+                Applicability::MachineApplicable,
+            );
+        }
+    }
+
+    /// Whether `operand` is a local variable that is used in the body of the `if` whose condition
+    /// is `expr`, possibly through further `&&` operators.
+    fn is_used_in_if_body(&self, expr: &'tcx hir::Expr<'tcx>, operand: &hir::Expr<'_>) -> bool {
+        let local = match operand.kind {
+            hir::ExprKind::Path(hir::QPath::Resolved(None, path)) => match path.res {
+                Res::Local(hir_id) => hir_id,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        let hir = self.tcx.hir();
+        let mut expr = expr;
+        loop {
+            match expr.kind {
+                hir::ExprKind::Binary(op, ..) if op.node == hir::BinOpKind::And => {}
+                hir::ExprKind::DropTemps(..) => {}
+                hir::ExprKind::Match(_, arms, hir::MatchSource::IfDesugar { .. }) => {
+                    let mut finder = LocalUseFinder { local, found: false };
+                    finder.visit_expr(arms[0].body);
+                    return finder.found;
+                }
+                _ => return false,
+            }
+            expr = match hir.find(hir.get_parent_node(expr.hir_id)) {
+                Some(hir::Node::Expr(parent)) => parent,
+                _ => return false,
+            };
+        }
+    }
+
+    /// Lints on `&&` and `||` whose right-hand side diverges, unless they are a statement of their
+    /// own like `cond || panic!()`. Their value can then only be the one the left-hand side decides
+    /// on, so the operator is just hiding an `if`.
+    fn check_diverging_short_circuit_rhs(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if expr.span.from_expansion() {
+            return;
+        }
+        let hir = self.tcx.hir();
+        if let Some(hir::Node::Stmt(hir::Stmt { kind: hir::StmtKind::Semi(_), .. })) =
+            hir.find(hir.get_parent_node(expr.hir_id))
+        {
+            return;
+        }
+        // `a && b` only gets to `b` if `a` is `true`, so if `b` diverges, it evaluates to `false`.
+        let (value, negation) = match op.node {
+            hir::BinOpKind::And => (false, ""),
+            _ => (true, "!"),
+        };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::DIVERGING_SHORT_CIRCUIT_OPERANDS,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                let mut err = lint.build(&format!(
+                    "the right-hand side of `{}` diverges, so this always evaluates to `{}`",
+                    op.node.as_str(),
+                    value
+                ));
+                err.span_label(rhs_expr.span, "this never produces a value");
+                if let Ok(lhs) = self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+                    let lhs = if negation.is_empty() {
+                        lhs
+                    } else {
+                        parenthesize(lhs_expr, lhs, PREC_PREFIX)
+                    };
+                    err.help(&format!(
+                        "use `if {}{} {{ ... }}` for the control flow and `{}` for the value",
+                        negation, lhs, value
+                    ));
+                }
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints on `||` chains of three or more `==` comparisons of the same place, like
+    /// `c == 'a' || c == 'e' || c == 'i'`, to suggest `matches!` or `contains` instead. Only the
+    /// outermost `||` of a chain is linted.
+    fn check_equality_chain(&self, expr: &'tcx hir::Expr<'tcx>, op: hir::BinOp) {
+        if op.node != hir::BinOpKind::Or || expr.span.from_expansion() {
+            return;
+        }
+        let hir = self.tcx.hir();
+        if let hir::Node::Expr(parent) = hir.get(hir.get_parent_node(expr.hir_id)) {
+            if let hir::ExprKind::Binary(parent_op, ..) = parent.kind {
+                if parent_op.node == hir::BinOpKind::Or {
+                    return;
+                }
+            }
+        }
+        fn collect_comparisons<'hir>(
+            expr: &'hir hir::Expr<'hir>,
+            comparisons: &mut Vec<(&'hir hir::Expr<'hir>, &'hir hir::Expr<'hir>)>,
+        ) -> bool {
+            match expr.kind {
+                hir::ExprKind::Binary(op, lhs, rhs) if op.node == hir::BinOpKind::Or => {
+                    collect_comparisons(lhs, comparisons) && collect_comparisons(rhs, comparisons)
+                }
+                hir::ExprKind::Binary(op, lhs, rhs) if op.node == hir::BinOpKind::Eq => {
+                    comparisons.push((lhs, rhs));
+                    !expr.span.from_expansion()
+                }
+                _ => false,
+            }
+        }
+        let mut comparisons = vec![];
+        if !collect_comparisons(expr, &mut comparisons) || comparisons.len() < 3 {
+            return;
+        }
+        // The shared operand has to be a place, so that evaluating it once instead of once per
+        // comparison has no side effects to lose.
+        let values_compared_with = |shared: &hir::Expr<'_>| {
+            comparisons
+                .iter()
+                .map(|&(lhs, rhs)| {
+                    if is_same_place(lhs, shared) {
+                        Some(rhs)
+                    } else if is_same_place(rhs, shared) {
+                        Some(lhs)
+                    } else {
+                        None
+                    }
+                })
+                .collect::<Option<Vec<_>>>()
+        };
+        let (first_lhs, first_rhs) = comparisons[0];
+        let (shared, values) = match values_compared_with(first_lhs) {
+            Some(values) => (first_lhs, values),
+            None => match values_compared_with(first_rhs) {
+                Some(values) => (first_rhs, values),
+                None => return,
+            },
+        };
+        // Both `matches!` and `contains` need the values to have the type of the shared operand,
+        // which `==` doesn't.
+        let shared_ty = self.resolve_vars_if_possible(&self.node_ty(shared.hir_id));
+        if shared_ty.references_error()
+            || values.iter().any(|value| {
+                self.resolve_vars_if_possible(&self.node_ty(value.hir_id)) != shared_ty
+            })
+        {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let snippets = values
+            .iter()
+            .map(|value| source_map.span_to_snippet(value.span).ok())
+            .collect::<Option<Vec<_>>>();
+        let (shared_snippet, snippets) = match (source_map.span_to_snippet(shared.span), snippets) {
+            (Ok(shared_snippet), Some(snippets)) => (shared_snippet, snippets),
+            _ => return,
+        };
+        // Literals and paths to constants are patterns with the same meaning, except for float
+        // literals, which are not allowed as patterns.
+        let is_pattern = |value: &hir::Expr<'_>| match value.kind {
+            hir::ExprKind::Lit(ref lit) => !matches!(lit.node, ast::LitKind::Float(..)),
+            hir::ExprKind::Unary(hir::UnOp::UnNeg, ref inner) => int_lit_value(inner).is_some(),
+            hir::ExprKind::Path(ref qpath) => matches!(
+                self.tables.borrow().qpath_res(qpath, value.hir_id),
+                Res::Def(DefKind::Const, _)
+                    | Res::Def(DefKind::AssocConst, _)
+                    | Res::Def(DefKind::Ctor(_, CtorKind::Const), _)
+            ),
+            _ => false,
+        };
+        let only_literals =
+            values.iter().all(|value| !matches!(value.kind, hir::ExprKind::Path(_)));
+        let (msg, suggestion, applicability) = if values.iter().all(|value| is_pattern(value)) {
+            // Constants used as patterns must also derive `PartialEq` and `Eq`.
+            (
+                "use `matches!` to compare with all values at once",
+                format!("matches!({}, {})", shared_snippet, snippets.join(" | ")),
+                if only_literals {
+                    Applicability::MachineApplicable
+                } else {
+                    Applicability::MaybeIncorrect
+                },
+            )
+        } else {
+            // All the values are evaluated up front, which may not be what the chain did.
+            (
+                "use `contains` to compare with all values at once",
+                format!("[{}].contains(&{})", snippets.join(", "), shared_snippet),
+                Applicability::MaybeIncorrect,
+            )
+        };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::EQUALITY_CHAINS,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                lint.build(&format!(
+                    "`{}` is compared with {} values one by one",
+                    shared_snippet,
+                    values.len()
+                ))
+                .span_suggestion(expr.span, msg, suggestion, applicability)
+                .emit();
+            },
+        );
+    }
+
+    /// Lints on calls like `a.add(b)` of the method of a binary operator trait, which can be
+    /// written as `a + b`. Calls that adjust an operand, like auto-referencing the receiver, are
+    /// left alone, since the operator doesn't.
+    pub fn check_binary_op_method_call(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        args: &'tcx [hir::Expr<'tcx>],
+        method_def_id: DefId,
+    ) {
+        if expr.span.from_expansion() || args.len() != 2 {
+            return;
+        }
+        let trait_def_id = match self.tcx.trait_of_item(method_def_id) {
+            Some(trait_def_id) => trait_def_id,
+            None => return,
+        };
+        let lang = self.tcx.lang_items();
+        let operators = [
+            (lang.add_trait(), hir::BinOpKind::Add),
+            (lang.sub_trait(), hir::BinOpKind::Sub),
+            (lang.mul_trait(), hir::BinOpKind::Mul),
+            (lang.div_trait(), hir::BinOpKind::Div),
+            (lang.rem_trait(), hir::BinOpKind::Rem),
+            (lang.bitxor_trait(), hir::BinOpKind::BitXor),
+            (lang.bitand_trait(), hir::BinOpKind::BitAnd),
+            (lang.bitor_trait(), hir::BinOpKind::BitOr),
+            (lang.shl_trait(), hir::BinOpKind::Shl),
+            (lang.shr_trait(), hir::BinOpKind::Shr),
+        ];
+        let op = match operators.iter().find(|(op_trait, _)| *op_trait == Some(trait_def_id)) {
+            Some(&(_, op)) => op,
+            None => return,
+        };
+        let (lhs_expr, rhs_expr) = (&args[0], &args[1]);
+        {
+            let tables = self.tables.borrow();
+            if !tables.expr_adjustments(lhs_expr).is_empty()
+                || !tables.expr_adjustments(rhs_expr).is_empty()
+            {
+                return;
+            }
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (lhs, rhs) = match (
+            source_map.span_to_snippet(lhs_expr.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(lhs), Ok(rhs)) => (lhs, rhs),
+            _ => return,
+        };
+        // A receiver that binds less tightly than a method call is already parenthesized, and its
+        // span includes the parentheses.
+        let prec = AssocOp::from_ast_binop(op.into()).precedence() as i8;
+        let rhs = if rhs_expr.precedence().order() <= prec { format!("({})", rhs) } else { rhs };
+        // The call binds tighter than the operator, so the operation may need parentheses where
+        // the call is used as an operand itself.
+        let hir = self.tcx.hir();
+        let needs_parens = match hir.find(hir.get_parent_node(expr.hir_id)) {
+            Some(hir::Node::Expr(parent)) => match parent.kind {
+                hir::ExprKind::Binary(parent_op, parent_lhs, _) => {
+                    let parent_prec =
+                        AssocOp::from_ast_binop(parent_op.node.into()).precedence() as i8;
+                    parent_prec > prec || parent_prec == prec && parent_lhs.hir_id != expr.hir_id
+                }
+                hir::ExprKind::MethodCall(_, _, parent_args) => {
+                    parent_args[0].hir_id == expr.hir_id
+                }
+                hir::ExprKind::Index(base, _) => base.hir_id == expr.hir_id,
+                hir::ExprKind::Unary(..)
+                | hir::ExprKind::Cast(..)
+                | hir::ExprKind::Type(..)
+                | hir::ExprKind::AddrOf(..)
+                | hir::ExprKind::Field(..) => true,
+                _ => false,
+            },
+            _ => false,
+        };
+        let operation = format!("{} {} {}", lhs, op.as_str(), rhs);
+        let operation = if needs_parens { format!("({})", operation) } else { operation };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::BINARY_OP_METHOD_CALL_PARITY,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                lint.build(&format!(
+                    "this call of `{}` can be written with the `{}` operator",
+                    self.tcx.def_path_str(method_def_id),
+                    op.as_str()
+                ))
+                .span_suggestion(
+                    expr.span,
+                    "use the operator",
+                    operation,
+                    Applicability::MachineApplicable,
+                )
+                .emit();
+            },
+        );
+    }
+
+    /// Lints on chains like `a - b - c` and `a / b / c`, whose operators are not associative, to
+    /// suggest making the left-to-right evaluation explicit with parentheses. Only the outermost
+    /// operation of a chain is linted.
+    fn check_op_associativity_hint(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(op.node, hir::BinOpKind::Sub | hir::BinOpKind::Div)
+            || expr.span.from_expansion()
+        {
+            return;
+        }
+        // Parentheses are gone in the HIR, but a parenthesized operation starts before its
+        // left-hand side.
+        let chained = |outer: &hir::Expr<'_>| match outer.kind {
+            hir::ExprKind::Binary(outer_op, outer_lhs, _) => {
+                outer_op.node == op.node && outer.span.lo() == outer_lhs.span.lo()
+            }
+            _ => false,
+        };
+        // The operations on the left of this one that are part of the chain.
+        let mut chain = vec![];
+        let mut operand = lhs_expr;
+        while let hir::ExprKind::Binary(_, inner_lhs, _) = operand.kind {
+            if !chained(operand) {
+                break;
+            }
+            chain.push(operand);
+            operand = inner_lhs;
+        }
+        let (inner_lhs, inner_rhs) = match lhs_expr.kind {
+            hir::ExprKind::Binary(_, inner_lhs, inner_rhs) if !chain.is_empty() => {
+                (inner_lhs, inner_rhs)
+            }
+            _ => return,
+        };
+        let hir = self.tcx.hir();
+        if let hir::Node::Expr(parent) = hir.get(hir.get_parent_node(expr.hir_id)) {
+            if let hir::ExprKind::Binary(parent_op, parent_lhs, _) = parent.kind {
+                if parent_op.node == op.node && parent_lhs.hir_id == expr.hir_id && chained(expr) {
+                    return;
+                }
+            }
+        }
+        let source_map = self.tcx.sess.source_map();
+        let snippets = (
+            source_map.span_to_snippet(inner_lhs.span),
+            source_map.span_to_snippet(inner_rhs.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        );
+        let (a, b, c) = match snippets {
+            (Ok(a), Ok(b), Ok(c)) => (a, b, c),
+            _ => return,
+        };
+        let mut parens = vec![(lhs_expr.span.shrink_to_lo(), "(".repeat(chain.len()))];
+        parens.extend(
+            chain.iter().rev().map(|operation| (operation.span.shrink_to_hi(), ")".to_string())),
+        );
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::OP_ASSOCIATIVITY,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                let op = op.node.as_str();
+                let mut err = lint.build(&format!("`{}` is evaluated from left to right", op));
+                err.multipart_suggestion(
+                    "add parentheses to make the order of evaluation explicit",
+                    parens,
+                    Applicability::MachineApplicable,
+                );
+                err.note(&format!(
+                    "if `{b} {op} {c}` was meant to be evaluated first, write \
+                     `{a} {op} ({b} {op} {c})`",
+                    a = a,
+                    b = b,
+                    c = c,
+                    op = op,
+                ));
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints `x -= 1` on unsigned integers, which underflows when `x` is `0`.
+    fn check_potential_underflow(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if op.node != hir::BinOpKind::Sub
+            || expr.span.from_expansion()
+            || int_lit_value(rhs_expr) != Some(1)
+        {
+            return;
+        }
+        let lhs_ty = self.resolve_vars_if_possible(&lhs_ty);
+        if !matches!(lhs_ty.kind, Uint(_)) {
+            return;
+        }
+        let lhs = match self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+            Ok(lhs) => lhs,
+            Err(_) => return,
+        };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::POTENTIAL_UNDERFLOW,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                let mut err =
+                    lint.build(&format!("this subtraction underflows if `{}` is `0`", lhs));
+                err.note(
+                    "it panics if overflow checks are enabled, as they are in debug builds, and \
+                     wraps around otherwise",
+                );
+                let receiver = parenthesize(lhs_expr, lhs.clone(), PREC_POSTFIX);
+                err.span_suggestion(
+                    expr.span,
+                    "use `saturating_sub` to stop at `0`",
+                    format!("{} = {}.saturating_sub(1)", lhs, receiver),
+                    Applicability::MaybeIncorrect,
+                );
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints on `!a == b` and `!a != b` with a boolean place `a`. The `!` only applies to `a`,
+    /// which for booleans gives the same result as negating the comparison, but is easily misread.
+    fn check_negated_equality_operand(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        let flipped = match op.node {
+            hir::BinOpKind::Eq => hir::BinOpKind::Ne,
+            hir::BinOpKind::Ne => hir::BinOpKind::Eq,
+            _ => return,
+        };
+        let operand = match lhs_expr.kind {
+            hir::ExprKind::Unary(hir::UnOp::UnNot, operand) => operand,
+            _ => return,
+        };
+        if expr.span.from_expansion()
+            || lhs_expr.span.from_expansion()
+            || !self.resolve_vars_if_possible(&lhs_ty).is_bool()
+            || !matches!(operand.kind, hir::ExprKind::Path(_) | hir::ExprKind::Field(..))
+        {
+            return;
+        }
+        let source_map = self.tcx.sess.source_map();
+        let (operand_snippet, rhs_snippet) = match (
+            source_map.span_to_snippet(operand.span),
+            source_map.span_to_snippet(rhs_expr.span),
+        ) {
+            (Ok(operand), Ok(rhs)) => (operand, rhs),
+            _ => return,
+        };
+        // Parentheses are not in the HIR, but they are included in the spans of the expressions
+        // they surround, so `(!a) == b` and `!(a) == b` show up as the snippets starting with one.
+        if operand_snippet.starts_with('(')
+            || source_map.span_to_snippet(lhs_expr.span).map_or(true, |lhs| lhs.starts_with('('))
+        {
+            return;
+        }
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::NEGATED_EQUALITY_OPERANDS,
+            expr.hir_id,
+            expr.span,
+            |lint| {
+                let mut err = lint.build(&format!(
+                    "`!` applies to `{}` only, not to the `{}` comparison",
+                    operand_snippet,
+                    op.node.as_str()
+                ));
+                err.note(&format!(
+                    "for booleans, negating either side of `{}` is the same as negating the \
+                     comparison",
+                    op.node.as_str()
+                ));
+                err.span_suggestion(
+                    expr.span,
+                    &format!("use `{}` instead of negating an operand", flipped.as_str()),
+                    format!("{} {} {}", operand_snippet, flipped.as_str(), rhs_snippet),
+                    Applicability::MachineApplicable,
+                );
+                err.span_suggestion(
+                    lhs_expr.span,
+                    "or make it clear that only the operand is negated",
+                    format!("(!{})", operand_snippet),
+                    Applicability::MaybeIncorrect,
+                );
+                err.emit();
+            },
+        );
+    }
+
+    /// Lints on builtin integer operations with an operand that is the identity element of the
+    /// operation, such as `x | 0` or `x * 1`, which evaluate to their other operand.
+    fn check_identity_op(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        rhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        let (identity, commutative) = match op.node {
+            hir::BinOpKind::Add | hir::BinOpKind::BitOr | hir::BinOpKind::BitXor => (0, true),
+            hir::BinOpKind::Sub | hir::BinOpKind::Shl | hir::BinOpKind::Shr => (0, false),
+            hir::BinOpKind::Mul => (1, true),
+            hir::BinOpKind::Div => (1, false),
+            _ => return,
+        };
+        if expr.span.from_expansion()
+            || lhs_expr.span.from_expansion()
+            || rhs_expr.span.from_expansion()
+            // With references, the remaining operand would not have the type of the result.
+            || !self.resolve_vars_if_possible(&lhs_ty).is_integral()
+            || !self.resolve_vars_if_possible(&rhs_ty).is_integral()
+        {
+            return;
+        }
+        // Finding out the value of a named constant means evaluating it, which is only worth it
+        // when the lint is going to be emitted.
+        let (level, _) = self.tcx.lint_level_at_node(lint::builtin::IDENTITY_OPS, expr.hir_id);
+        if level == lint::Level::Allow {
+            return;
+        }
+        let mut operands = vec![(rhs_expr, lhs_expr)];
+        if commutative {
+            operands.push((lhs_expr, rhs_expr));
+        }
+        for (identity_expr, kept_expr) in operands {
+            let constant = match self.identity_operand(identity_expr, identity) {
+                Some(constant) => constant,
+                None => continue,
+            };
+            self.tcx.struct_span_lint_hir(
+                lint::builtin::IDENTITY_OPS,
+                expr.hir_id,
+                expr.span,
+                |lint| {
+                    let op = op.node.as_str();
+                    match constant {
+                        // The constant might only be the identity in some configurations, so it is
+                        // pointed out rather than suggested away.
+                        Some(def_id) => {
+                            let name = self.tcx.def_path_str(def_id);
+                            lint.build(&format!(
+                                "this `{}` operation has no effect, as `{}` is `{}`",
+                                op, name, identity
+                            ))
+                            .span_label(identity_expr.span, format!("`{}` is `{}`", name, identity))
+                            .emit();
+                        }
+                        None => {
+                            let mut err = lint.build(&format!(
+                                "this `{}` operation has no effect, as one operand is `{}`",
+                                op, identity
+                            ));
+                            let source_map = self.tcx.sess.source_map();
+                            if let Ok(snippet) = source_map.span_to_snippet(kept_expr.span) {
+                                err.span_suggestion(
+                                    expr.span,
+                                    "remove the operation",
+                                    snippet,
+                                    Applicability::MachineApplicable,
+                                );
+                            }
+                            err.emit();
+                        }
+                    }
+                },
+            );
+            return;
+        }
+    }
+
+    /// Whether `expr` is the integer `identity`, either as a literal, in which case this returns
+    /// `Some(None)`, or as a named constant, in which case it returns the constant's `DefId`.
+    fn identity_operand(&self, expr: &hir::Expr<'_>, identity: u128) -> Option<Option<DefId>> {
+        match expr.kind {
+            hir::ExprKind::Lit(ref lit) => match lit.node {
+                ast::LitKind::Int(value, _) if value == identity => Some(None),
+                _ => None,
+            },
+            // Associated constants are left out, as their value can depend on generic parameters.
+            hir::ExprKind::Path(hir::QPath::Resolved(None, path)) => match path.res {
+                Res::Def(DefKind::Const, def_id) => {
+                    let ty = self.tcx.type_of(def_id);
+                    if !ty.is_integral() {
+                        return None;
+                    }
+                    let size = self.tcx.layout_of(ty::ParamEnv::reveal_all().and(ty)).ok()?.size;
+                    let value = self.tcx.const_eval_poly(def_id).ok()?.try_to_bits(size)?;
+                    if value == identity { Some(Some(def_id)) } else { None }
+                }
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// Lints on shifts by a negative integer literal, like `x << -1`, which never shift in the
+    /// direction of the operator, and suggests shifting the other way instead.
+    fn check_shift_by_negative(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if !matches!(BinOpCategory::from(op), BinOpCategory::Shift) || expr.span.from_expansion() {
+            return;
+        }
+        let amount = match shift_amount(rhs_expr) {
+            Some((true, amount)) if amount != 0 => amount,
+            _ => return,
+        };
+        let is_assign = matches!(expr.kind, hir::ExprKind::AssignOp(..));
+        let (direction, flipped) = match op.node {
+            hir::BinOpKind::Shl => ("right", ">>"),
+            _ => ("left", "<<"),
+        };
+        let flipped = if is_assign { format!("{}=", flipped) } else { flipped.to_string() };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::NEGATIVE_SHIFT,
+            expr.hir_id,
+            rhs_expr.span,
+            |lint| {
+                let mut err = lint.build("shifting by a negative amount");
+                err.note(
+                    "it panics if overflow checks are enabled, as they are in debug builds, and \
+                     otherwise shifts by the amount modulo the bit width of the type",
+                );
+                err.multipart_suggestion(
+                    &format!("to shift {} by `{}`, use `{}`", direction, amount, flipped),
+                    vec![(op.span, flipped.clone()), (rhs_expr.span, amount.to_string())],
+                    Applicability::MaybeIncorrect,
+                );
+                err.emit();
+            },
+        );
+    }
+
+    /// Remembers a shift by a literal amount, so that `check_deferred_shift_overflows` can lint
+    /// on it once integer fallback has settled the type of the shifted value.
+    fn defer_shift_overflow_check(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        rhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_ty: Ty<'tcx>,
+        op: hir::BinOp,
+    ) {
+        if matches!(BinOpCategory::from(op), BinOpCategory::Shift)
+            && shift_amount(rhs_expr).is_some()
+        {
+            self.deferred_shift_checks.borrow_mut().push((expr, lhs_ty));
+        }
+    }
+
+    /// Lints on the shifts deferred by `defer_shift_overflow_check` whose amount is negative or
+    /// not less than the bit width of the shifted type. MIR const propagation leaves shifts by a
+    /// literal amount to this check, so they are only reported once.
+    pub fn check_deferred_shift_overflows(&self) {
+        let deferred = mem::take(&mut *self.deferred_shift_checks.borrow_mut());
+        for (expr, lhs_ty) in deferred {
+            self.check_shift_overflow(expr, lhs_ty);
+        }
+    }
+
+    fn check_shift_overflow(&self, expr: &'tcx hir::Expr<'tcx>, lhs_ty: Ty<'tcx>) {
+        let (op, lhs_expr, rhs_expr, is_assign) = match expr.kind {
+            hir::ExprKind::Binary(op, ref lhs, ref rhs) => (op, lhs, rhs, false),
+            hir::ExprKind::AssignOp(op, ref lhs, ref rhs) => (op, lhs, rhs, true),
+            _ => return,
+        };
+        let lhs_ty = deref_ty_if_possible(self.resolve_vars_if_possible(&lhs_ty));
+        let pointer_bits = self.tcx.data_layout.pointer_size.bits();
+        let bits = match lhs_ty.kind {
+            ty::Int(ity) => ity.bit_width().unwrap_or(pointer_bits),
+            ty::Uint(uty) => uty.bit_width().unwrap_or(pointer_bits),
+            _ => return,
+        };
+        let (negative, amount) = match shift_amount(rhs_expr) {
+            Some(amount) => amount,
+            None => return,
+        };
+        if !((negative && amount != 0) || amount >= u128::from(bits))
+            || !self.is_const_propagated(expr.hir_id)
+        {
+            return;
+        }
+
+        let (direction, rotate, checked) = match op.node {
+            hir::BinOpKind::Shl => ("left", "rotate_left", "checked_shl"),
+            _ => ("right", "rotate_right", "checked_shr"),
+        };
+        let amount = if negative { format!("-{}", amount) } else { amount.to_string() };
+        self.tcx.struct_span_lint_hir(
+            lint::builtin::ARITHMETIC_OVERFLOW,
+            expr.hir_id,
+            rhs_expr.span,
+            |lint| {
+                let mut err = lint.build(&format!(
+                    "this arithmetic operation will overflow: `{}` is only {} bits wide",
+                    lhs_ty, bits
+                ));
+                err.span_label(
+                    rhs_expr.span,
+                    format!("attempt to shift {} by `{}`", direction, amount),
+                );
+                if !negative && !is_assign && !expr.span.from_expansion() {
+                    if let Ok(lhs) = self.tcx.sess.source_map().span_to_snippet(lhs_expr.span) {
+                        let lhs = if lhs.starts_with('(') && lhs.ends_with(')') {
+                            lhs
+                        } else {
+                            parenthesize(lhs_expr, lhs, PREC_POSTFIX)
+                        };
+                        err.span_suggestions(
+                            expr.span,
+                            &format!(
+                                "if a rotation or a checked shift was intended, use `{}` or `{}`",
+                                rotate, checked
+                            ),
+                            vec![
+                                format!("{}.{}({})", lhs, rotate, amount),
+                                format!("{}.{}({})", lhs, checked, amount),
+                            ]
+                            .into_iter(),
+                            Applicability::MaybeIncorrect,
+                        );
+                    }
+                }
+                err.emit();
+            },
+        );
+    }
+
+    /// Whether MIR const propagation runs on the body containing `hir_id`, which it only does for
+    /// functions, closures and associated constants.
+    fn is_const_propagated(&self, hir_id: hir::HirId) -> bool {
+        let hir = self.tcx.hir();
+        let owner = hir.parent_iter(hir_id).find(|&(id, _)| hir.maybe_body_owned_by(id).is_some());
+        match owner {
+            Some((id, node)) => {
+                FnLikeNode::from_node(node).is_some()
+                    || self.tcx.def_kind(hir.local_def_id(id).to_def_id()) == DefKind::AssocConst
+            }
+            None => false,
+        }
+    }
 
-        (lhs_ty, rhs_ty, return_ty)
+    /// Whether `ty` is fully inferred and known to be zero-sized.
+    fn is_known_zst(&self, ty: Ty<'tcx>) -> bool {
+        let ty = self.resolve_vars_if_possible(&ty);
+        if ty.needs_infer() || ty.references_error() {
+            return false;
+        }
+        let ty = self.tcx.erase_regions(&ty);
+        self.tcx.layout_of(self.param_env.and(ty)).map_or(false, |layout| layout.is_zst())
+    }
+
+    /// Renders an operand type for the message and labels of an operator error. Unless `-Z verbose`
+    /// is passed, overly long type names are shortened and written to a file in full, whose path
+    /// is collected into `long_ty_paths`.
+    fn operand_ty_string(&self, ty: Ty<'tcx>, long_ty_paths: &mut Vec<PathBuf>) -> String {
+        let ty_str = ty.to_string();
+        if ty_str.len() <= LONG_OPERAND_TY_LEN || self.tcx.sess.verbose() {
+            return ty_str;
+        }
+        let shortened = elide_nested_generic_args(&ty_str);
+        if shortened.len() >= ty_str.len() {
+            return ty_str;
+        }
+        let mut hasher = DefaultHasher::new();
+        ty_str.hash(&mut hasher);
+        let path = self
+            .tcx
+            .output_filenames(LOCAL_CRATE)
+            .temp_path_ext(&format!("long-type-{}.txt", hasher.finish()), None);
+        if std::fs::write(&path, &ty_str).is_err() {
+            return ty_str;
+        }
+        if !long_ty_paths.contains(&path) {
+            long_ty_paths.push(path);
+        }
+        shortened
     }
 
     /// If one of the types is an uncalled function and calling it would yield the other type,
     /// suggest calling the function. Returns `true` if suggestion would apply (even if not given).
+    /// Neither the label nor the suggestion are added if `span` can't be pointed at.
     fn add_type_neq_err_label(
         &self,
         err: &mut rustc_errors::DiagnosticBuilder<'_>,
         span: Span,
+        is_visible: bool,
         ty: Ty<'tcx>,
+        ty_str: &str,
         other_ty: Ty<'tcx>,
         op: hir::BinOp,
         is_assign: IsAssign,
     ) -> bool /* did we suggest to call a function because of missing parenthesis? */ {
-        err.span_label(span, ty.to_string());
+        if is_visible {
+            err.span_label(span, ty_str);
+        }
         if let FnDef(def_id, _) = ty.kind {
             let source_map = self.tcx.sess.source_map();
             if !self.tcx.has_typeck_tables(def_id) {
@@ -538,7 +4184,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                 .lookup_op_method(fn_sig.output(), &[other_ty], Op::Binary(op, is_assign))
                 .is_ok()
             {
-                if let Ok(snippet) = source_map.span_to_snippet(span) {
+                let snippet = if is_visible { source_map.span_to_snippet(span).ok() } else { None };
+                if let Some(snippet) = snippet {
                     let (variable_snippet, applicability) = if !fn_sig.inputs().is_empty() {
                         (format!("{}( /* arguments */ )", snippet), Applicability::HasPlaceholders)
                     } else {
@@ -586,6 +4233,12 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
 
         let is_std_string = |ty| &format!("{:?}", ty) == "std::string::String";
 
+        // Operands written by a macro can't be rewritten from their snippets, so only explain
+        // the fix for them.
+        let from_expansion = lhs_expr.span.from_expansion() || rhs_expr.span.from_expansion();
+        // An operator written by a macro is reported at the call of the macro instead.
+        let label_op = macro_call_site(op.span).is_none();
+
         match (&lhs_ty.kind, &rhs_ty.kind) {
             (&Ref(_, l_ty, _), &Ref(_, r_ty, _)) // &str or &String + &str, &String or &&str
                 if (l_ty.kind == Str || is_std_string(l_ty)) && (
@@ -594,26 +4247,18 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                     ) =>
             {
                 if !is_assign { // Do not supply this message if `&str += &str`
-                    err.span_label(
-                        op.span,
-                        "`+` cannot be used to concatenate two `&str` strings",
-                    );
-                    match source_map.span_to_snippet(lhs_expr.span) {
-                        Ok(lstring) => {
+                    if label_op {
+                        err.span_label(
+                            op.span,
+                            "`+` cannot be used to concatenate two `&str` strings",
+                        );
+                    }
+                    match self.str_addition_owned_lhs(lhs_expr, is_std_string(l_ty)) {
+                        Some((owned_lhs, removed_borrow)) if !from_expansion => {
                             err.span_suggestion(
                                 lhs_expr.span,
-                                if lstring.starts_with('&') {
-                                    remove_borrow_msg
-                                } else {
-                                    msg
-                                },
-                                if lstring.starts_with('&') {
-                                    // let a = String::new();
-                                    // let _ = &a + "bar";
-                                    lstring[1..].to_string()
-                                } else {
-                                    format!("{}.to_owned()", lstring)
-                                },
+                                if removed_borrow { remove_borrow_msg } else { msg },
+                                owned_lhs,
                                 Applicability::MachineApplicable,
                             )
                         }
@@ -625,27 +4270,22 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
             (&Ref(_, l_ty, _), &Adt(..)) // Handle `&str` & `&String` + `String`
                 if (l_ty.kind == Str || is_std_string(l_ty)) && is_std_string(rhs_ty) =>
             {
-                err.span_label(
-                    op.span,
-                    "`+` cannot be used to concatenate a `&str` with a `String`",
-                );
+                if label_op {
+                    err.span_label(
+                        op.span,
+                        "`+` cannot be used to concatenate a `&str` with a `String`",
+                    );
+                }
                 match (
-                    source_map.span_to_snippet(lhs_expr.span),
+                    self.str_addition_owned_lhs(lhs_expr, is_std_string(l_ty)),
                     source_map.span_to_snippet(rhs_expr.span),
-                    is_assign,
+                    is_assign || from_expansion,
                 ) {
-                    (Ok(l), Ok(r), false) => {
-                        let to_string = if l.starts_with('&') {
-                            // let a = String::new(); let b = String::new();
-                            // let _ = &a + b;
-                            l[1..].to_string()
-                        } else {
-                            format!("{}.to_owned()", l)
-                        };
+                    (Some((owned_lhs, _)), Ok(r), false) => {
                         err.multipart_suggestion(
                             msg,
                             vec![
-                                (lhs_expr.span, to_string),
+                                (lhs_expr.span, owned_lhs),
                                 (rhs_expr.span, format!("&{}", r)),
                             ],
                             Applicability::MachineApplicable,
@@ -661,6 +4301,33 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
+    /// Returns the source of an owned `String` to replace the LHS of a string concatenation with,
+    /// and whether it was obtained by removing the borrow of a `String` rather than by calling
+    /// `to_owned` on the LHS.
+    fn str_addition_owned_lhs(
+        &self,
+        lhs_expr: &'tcx hir::Expr<'tcx>,
+        lhs_borrows_string: bool,
+    ) -> Option<(String, bool)> {
+        let source_map = self.tcx.sess.source_map();
+        match lhs_expr.kind {
+            // let a = String::new();
+            // let _ = &a + "bar";
+            hir::ExprKind::AddrOf(hir::BorrowKind::Ref, hir::Mutability::Not, inner)
+                if lhs_borrows_string =>
+            {
+                Some((source_map.span_to_snippet(inner.span).ok()?, true))
+            }
+            _ => {
+                let lstring = source_map.span_to_snippet(lhs_expr.span).ok()?;
+                Some((
+                    format!("{}.to_owned()", parenthesize(lhs_expr, lstring, PREC_POSTFIX)),
+                    false,
+                ))
+            }
+        }
+    }
+
     pub fn check_user_unop(
         &self,
         ex: &'tcx hir::Expr<'tcx>,
@@ -692,6 +4359,8 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                             op.as_str()
                         ),
                     );
+                    self.suggest_deref_manually_drop_operand(&mut err, ex, actual, op);
+                    self.suggest_cell_get_for_unop(&mut err, ex, actual, op);
                     match actual.kind {
                         Uint(_) if op == hir::UnOp::UnNeg => {
                             err.note("unsigned values cannot be negated");
@@ -704,7 +4373,7 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
                                 hir::UnOp::UnNot => "std::ops::Not",
                                 hir::UnOp::UnDeref => "std::ops::UnDerf",
                             };
-                            suggest_impl_missing(&mut err, operand_ty, &missing_trait);
+                            suggest_impl_missing(self.tcx, &mut err, operand_ty, &missing_trait);
                         }
                     }
                     err.emit();
@@ -714,89 +4383,488 @@ impl<'a, 'tcx> FnCtxt<'a, 'tcx> {
         }
     }
 
-    fn lookup_op_method(
+    /// Reports indexing a string by an integer, e.g. with `"hello"[0]`, explaining that strings are
+    /// UTF-8 and how to read a byte, a `char` or a string slice instead, starting with the one of
+    /// the `expected` type. Returns whether the error was reported.
+    fn check_op_on_str_index(
         &self,
-        lhs_ty: Ty<'tcx>,
-        other_tys: &[Ty<'tcx>],
-        op: Op,
-    ) -> Result<MethodCallee<'tcx>, ()> {
-        let lang = self.tcx.lang_items();
+        expr: &'tcx hir::Expr<'tcx>,
+        base: &'tcx hir::Expr<'tcx>,
+        idx: &'tcx hir::Expr<'tcx>,
+        base_t: Ty<'tcx>,
+        idx_t: Ty<'tcx>,
+        expected: Expectation<'tcx>,
+        needs: Needs,
+    ) -> bool {
+        let idx_t = self.resolve_vars_if_possible(&idx_t);
+        if !matches!(idx_t.kind, ty::Int(_) | Uint(_) | ty::Infer(ty::IntVar(_))) {
+            return false;
+        }
+        // `String` only implements `Index` for ranges, so it is indexed as a `str` too.
+        let is_std_string = |ty: Ty<'tcx>| &format!("{:?}", ty) == "std::string::String";
+        let base_t = self.resolve_vars_if_possible(&base_t).peel_refs();
+        if base_t.kind != Str && !is_std_string(base_t) {
+            return false;
+        }
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            expr.span,
+            E0277,
+            "the type `str` cannot be indexed by `{}`",
+            idx_t
+        );
+        err.span_label(expr.span, "string indices are ranges of `usize`");
+        err.note(
+            "strings are UTF-8, where a character can take up more than one byte, so a position \
+             in a string is a byte offset that isn't necessarily the start of a character",
+        );
+        err.note(
+            "for more information, see \
+             <https://doc.rust-lang.org/book/ch08-02-strings.html#indexing-into-strings>",
+        );
+        let source_map = self.tcx.sess.source_map();
+        if let (Needs::None, Ok(base_snippet), Ok(idx_snippet)) =
+            (needs, source_map.span_to_snippet(base.span), source_map.span_to_snippet(idx.span))
+        {
+            let receiver = parenthesize(base, base_snippet, PREC_POSTFIX);
+            let end = match idx.kind {
+                hir::ExprKind::Lit(ref lit) => match lit.node {
+                    ast::LitKind::Int(i, _) => Some((i + 1).to_string()),
+                    _ => None,
+                },
+                _ => None,
+            };
+            let end = end.unwrap_or_else(|| format!("{} + 1", idx_snippet));
+            let mut suggestions = vec![
+                (
+                    "to get the byte at that offset, as a `u8`, index the bytes of the string",
+                    format!("{}.as_bytes()[{}]", receiver, idx_snippet),
+                ),
+                (
+                    "to get the character at that position instead, as an `Option<char>`, count \
+                     the characters with `chars().nth()`",
+                    format!("{}.chars().nth({})", receiver, idx_snippet),
+                ),
+                (
+                    "to get the string slice of the byte at that offset, index with a range, which \
+                     panics if the byte isn't a whole character",
+                    format!("&{}[{}..{}]", receiver, idx_snippet, end),
+                ),
+            ];
+            // Start with what the expression is expected to be, if that's known.
+            if let Some(expected) = expected.only_has_type(self) {
+                match self.resolve_vars_if_possible(&expected).kind {
+                    Char => suggestions.swap(0, 1),
+                    Ref(_, ty, _) if ty.kind == Str => suggestions.rotate_right(1),
+                    _ => {}
+                }
+            }
+            for (msg, sugg) in suggestions {
+                err.span_suggestion_verbose(expr.span, msg, sugg, Applicability::MaybeIncorrect);
+            }
+        }
+        err.emit();
+        true
+    }
 
-        let span = match op {
-            Op::Binary(op, _) => op.span,
-            Op::Unary(_, span) => span,
+    /// Reports indexing a slice, array or `Vec` with an integer that isn't a `usize`, suggesting
+    /// how to convert the index. Returns `true` if the error was emitted.
+    fn check_op_on_slice_index(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        idx: &'tcx hir::Expr<'tcx>,
+        base_t: Ty<'tcx>,
+        idx_t: Ty<'tcx>,
+    ) -> bool {
+        let idx_t = self.resolve_vars_if_possible(&idx_t);
+        // The width of an unsigned index, or `None` for a signed one.
+        let uint_bits = match idx_t.kind {
+            ty::Int(_) => None,
+            Uint(uty) if uty.bit_width().is_some() => uty.bit_width(),
+            _ => return false,
+        };
+        let elem_t = match self.resolve_vars_if_possible(&base_t).peel_refs().kind {
+            ty::Slice(elem) | Array(elem, _) => elem,
+            Adt(def, substs) if self.tcx.is_diagnostic_item(sym::vec_type, def.did) => {
+                substs.type_at(0)
+            }
+            _ => return false,
+        };
+        let slice_t = self.tcx.mk_slice(elem_t);
+        let mut err = struct_span_err!(
+            self.tcx.sess,
+            expr.span,
+            E0277,
+            "the type `{}` cannot be indexed by `{}`",
+            slice_t,
+            idx_t
+        );
+        err.span_label(expr.span, "slice indices are of type `usize` or ranges of `usize`");
+        err.help(&format!(
+            "the trait `std::slice::SliceIndex<{}>` is not implemented for `{}`",
+            slice_t, idx_t
+        ));
+        if let Ok(snippet) = self.tcx.sess.source_map().span_to_snippet(idx.span) {
+            let (msg, sugg) = match idx.kind {
+                hir::ExprKind::Lit(ref lit) if lit.node.is_suffixed() => (
+                    format!("change the type of the numeric literal from `{}` to `usize`", idx_t),
+                    format!("{}usize", snippet.trim_end_matches(&idx_t.to_string())),
+                ),
+                _ => {
+                    let operand = parenthesize(idx, snippet.clone(), PREC_PREFIX);
+                    match uint_bits {
+                        None => (
+                            "convert the index to a `usize`, which panics if it's negative or \
+                             doesn't fit"
+                                .to_string(),
+                            format!("usize::try_from({}).unwrap()", snippet),
+                        ),
+                        Some(bits) if bits <= 16 => (
+                            format!(
+                                "convert the index to a `usize`, which can hold any `u{}`",
+                                bits
+                            ),
+                            format!("usize::from({})", snippet),
+                        ),
+                        Some(bits) => {
+                            err.note(&format!(
+                                "on {}-bit targets, `as usize` truncates `{}` values larger than \
+                                 `u{}::MAX`",
+                                bits / 2,
+                                idx_t,
+                                bits / 2
+                            ));
+                            (
+                                "cast the index to a `usize`".to_string(),
+                                format!("{} as usize", operand),
+                            )
+                        }
+                    }
+                }
+            };
+            err.span_suggestion_verbose(idx.span, &msg, sugg, Applicability::MachineApplicable);
+        }
+        err.emit();
+        true
+    }
+
+    /// Explains how to access the elements of a tuple, or the fields of a tuple struct, that
+    /// is indexed with `[]`. Returns `true` if `base_t` is such a type.
+    fn diagnose_tuple_index(
+        &self,
+        err: &mut DiagnosticBuilder<'_>,
+        expr: &'tcx hir::Expr<'tcx>,
+        base: &'tcx hir::Expr<'tcx>,
+        idx: &'tcx hir::Expr<'tcx>,
+        base_t: Ty<'tcx>,
+    ) -> bool {
+        let base_t = self.resolve_vars_if_possible(&base_t).peel_refs();
+        let (elements, what) = match base_t.kind {
+            Tuple(tys) => (tys.types().collect::<Vec<_>>(), "tuple elements"),
+            Adt(def, substs) if def.is_struct() => {
+                let variant = def.non_enum_variant();
+                let accessible = variant.fields.iter().all(|field| {
+                    let def_scope = self
+                        .tcx
+                        .adjust_ident_and_get_scope(field.ident, variant.def_id, self.body_id)
+                        .1;
+                    field.vis.is_accessible_from(def_scope, self.tcx)
+                });
+                if variant.ctor_kind != CtorKind::Fn || !accessible {
+                    return false;
+                }
+                let fields = variant.fields.iter().map(|field| field.ty(self.tcx, substs));
+                (fields.collect(), "the fields of a tuple struct")
+            }
+            _ => return false,
         };
-        let (opname, trait_did) = if let Op::Binary(op, IsAssign::Yes) = op {
-            match op.node {
-                hir::BinOpKind::Add => ("add_assign", lang.add_assign_trait()),
-                hir::BinOpKind::Sub => ("sub_assign", lang.sub_assign_trait()),
-                hir::BinOpKind::Mul => ("mul_assign", lang.mul_assign_trait()),
-                hir::BinOpKind::Div => ("div_assign", lang.div_assign_trait()),
-                hir::BinOpKind::Rem => ("rem_assign", lang.rem_assign_trait()),
-                hir::BinOpKind::BitXor => ("bitxor_assign", lang.bitxor_assign_trait()),
-                hir::BinOpKind::BitAnd => ("bitand_assign", lang.bitand_assign_trait()),
-                hir::BinOpKind::BitOr => ("bitor_assign", lang.bitor_assign_trait()),
-                hir::BinOpKind::Shl => ("shl_assign", lang.shl_assign_trait()),
-                hir::BinOpKind::Shr => ("shr_assign", lang.shr_assign_trait()),
-                hir::BinOpKind::Lt
-                | hir::BinOpKind::Le
-                | hir::BinOpKind::Ge
-                | hir::BinOpKind::Gt
-                | hir::BinOpKind::Eq
-                | hir::BinOpKind::Ne
-                | hir::BinOpKind::And
-                | hir::BinOpKind::Or => {
-                    span_bug!(span, "impossible assignment operation: {}=", op.node.as_str())
-                }
-            }
-        } else if let Op::Binary(op, IsAssign::No) = op {
-            match op.node {
-                hir::BinOpKind::Add => ("add", lang.add_trait()),
-                hir::BinOpKind::Sub => ("sub", lang.sub_trait()),
-                hir::BinOpKind::Mul => ("mul", lang.mul_trait()),
-                hir::BinOpKind::Div => ("div", lang.div_trait()),
-                hir::BinOpKind::Rem => ("rem", lang.rem_trait()),
-                hir::BinOpKind::BitXor => ("bitxor", lang.bitxor_trait()),
-                hir::BinOpKind::BitAnd => ("bitand", lang.bitand_trait()),
-                hir::BinOpKind::BitOr => ("bitor", lang.bitor_trait()),
-                hir::BinOpKind::Shl => ("shl", lang.shl_trait()),
-                hir::BinOpKind::Shr => ("shr", lang.shr_trait()),
-                hir::BinOpKind::Lt => ("lt", lang.partial_ord_trait()),
-                hir::BinOpKind::Le => ("le", lang.partial_ord_trait()),
-                hir::BinOpKind::Ge => ("ge", lang.partial_ord_trait()),
-                hir::BinOpKind::Gt => ("gt", lang.partial_ord_trait()),
-                hir::BinOpKind::Eq => ("eq", lang.eq_trait()),
-                hir::BinOpKind::Ne => ("ne", lang.eq_trait()),
-                hir::BinOpKind::And | hir::BinOpKind::Or => {
-                    span_bug!(span, "&& and || are not overloadable")
+        if elements.is_empty() {
+            err.note(&format!("`{}` has no elements to access", base_t));
+            return true;
+        }
+        if let hir::ExprKind::Lit(ref lit) = idx.kind {
+            if let ast::LitKind::Int(i, _) = lit.node {
+                if i >= elements.len() as u128 {
+                    err.note(&format!(
+                        "`{}` only has {} element{}, so the last one is `.{}`",
+                        base_t,
+                        elements.len(),
+                        pluralize!(elements.len()),
+                        elements.len() - 1
+                    ));
+                } else if let Ok(snip) = self.tcx.sess.source_map().span_to_snippet(base.span) {
+                    // If the index is an integer, we can show the actual fixed expression.
+                    err.span_suggestion(
+                        expr.span,
+                        &format!("to access {}, use", what),
+                        format!("{}.{}", snip, i),
+                        Applicability::MachineApplicable,
+                    );
+                    return true;
                 }
             }
+        } else {
+            err.note(
+                "tuples can't be indexed dynamically, as their elements can have different types; \
+                 use a `match` on the index to access each element with its own code",
+            );
+            let first = self.resolve_vars_if_possible(&elements[0]);
+            let uniform = elements.iter().all(|&ty| {
+                let ty = self.resolve_vars_if_possible(&ty);
+                ty == first
+                    || matches!(
+                        (&ty.kind, &first.kind),
+                        (ty::Infer(ty::IntVar(_)), ty::Infer(ty::IntVar(_)))
+                            | (ty::Infer(ty::FloatVar(_)), ty::Infer(ty::FloatVar(_)))
+                    )
+            });
+            if uniform && elements.len() > 1 {
+                err.help(&format!(
+                    "the elements are all `{}`, so an array `[{0}; {}]` could be used instead, \
+                     which can be indexed dynamically",
+                    first,
+                    elements.len()
+                ));
+            }
+        }
+        err.help(&format!("to access {}, use tuple indexing syntax (e.g., `tuple.0`)", what));
+        true
+    }
+
+    /// Reports indexing a slice, array, `Vec` or `VecDeque` with a negative integer literal, as in
+    /// `v[-1]`, which some languages use to index from the end. Returns `true` if the error was
+    /// emitted.
+    fn check_negative_index(
+        &self,
+        base: &'tcx hir::Expr<'tcx>,
+        idx: &'tcx hir::Expr<'tcx>,
+        base_t: Ty<'tcx>,
+    ) -> bool {
+        let n = match idx.kind {
+            hir::ExprKind::Unary(hir::UnOp::UnNeg, operand) => match int_lit_value(operand) {
+                Some(n) if n > 0 => n,
+                _ => return false,
+            },
+            _ => return false,
+        };
+        let base_t = self.resolve_vars_if_possible(&base_t);
+        let last = match base_t.peel_refs().kind {
+            ty::Slice(_) | Array(..) => "last",
+            Adt(def, _) if self.tcx.is_diagnostic_item(sym::vec_type, def.did) => "last",
+            Adt(def, _) if self.tcx.is_diagnostic_item(sym::vecdeque_type, def.did) => "back",
+            _ => return false,
+        };
+        let mut err = self.tcx.sess.struct_span_err(
+            idx.span,
+            &format!("negative integers cannot be used to index on a `{}`", base_t),
+        );
+        err.span_label(idx.span, "Rust doesn't index from the end with negative integers");
+        if let Ok(base) = self.tcx.sess.source_map().span_to_snippet(base.span) {
+            err.span_suggestion_verbose(
+                idx.span,
+                "to index from the end, subtract from the length, which panics if it's too short",
+                format!("{}.len() - {}", base, n),
+                Applicability::MaybeIncorrect,
+            );
+            if n == 1 {
+                err.help(&format!(
+                    "`{}.{}()` gets the last element as an `Option`, which is `None` if there \
+                     isn't one",
+                    base, last
+                ));
+            } else {
+                err.help(&format!(
+                    "`{}.iter().rev().nth({})` gets the element as an `Option`, which is `None` \
+                     if there are fewer than {} elements",
+                    base,
+                    n - 1,
+                    n
+                ));
+            }
+        }
+        err.emit();
+        true
+    }
+
+    /// Checks `base[idx]`, where `base` has the type `base_t` and `idx` the type `idx_t`. If no
+    /// `Index` or `IndexMut` impl applies, the error suggests what is missing the same way as for
+    /// the other operators.
+    pub fn check_index_op(
+        &self,
+        expr: &'tcx hir::Expr<'tcx>,
+        base: &'tcx hir::Expr<'tcx>,
+        idx: &'tcx hir::Expr<'tcx>,
+        base_t: Ty<'tcx>,
+        idx_t: Ty<'tcx>,
+        expected: Expectation<'tcx>,
+        needs: Needs,
+    ) -> Ty<'tcx> {
+        if self.check_op_on_str_index(expr, base, idx, base_t, idx_t, expected, needs)
+            || self.check_negative_index(base, idx, base_t)
+            || self.check_op_on_slice_index(expr, idx, base_t, idx_t)
+        {
+            return self.tcx.types.err;
+        }
+        if let Some((index_ty, element_ty)) = self.lookup_indexing(expr, base, base_t, idx_t, needs)
+        {
+            // two-phase not needed because index_ty is never mutable
+            self.demand_coerce(idx, idx_t, index_ty, AllowTwoPhase::No);
+            return element_ty;
+        }
+
+        let mut err = type_error_struct!(
+            self.tcx.sess,
+            expr.span,
+            base_t,
+            E0608,
+            "cannot index into a value of type `{}`",
+            base_t
+        );
+        // Try to give some advice about indexing tuples.
+        if self.diagnose_tuple_index(&mut err, expr, base, idx, base_t) {
+            err.emit();
+            return self.tcx.types.err;
+        }
+
+        // An integer literal index is most likely meant to be a `usize`, which is what it would
+        // be inferred to once the bound is there. Other inference variables can't be named.
+        let idx_t = self.resolve_vars_if_possible(&idx_t);
+        let idx_t =
+            if let ty::Infer(ty::IntVar(_)) = idx_t.kind { self.tcx.types.usize } else { idx_t };
+        if idx_t.needs_infer() {
+            err.emit();
+            return self.tcx.types.err;
+        }
+        let missing_trait = match needs {
+            Needs::MutPlace => format!("std::ops::IndexMut<{}>", idx_t),
+            Needs::None => format!("std::ops::Index<{}>", idx_t),
+        };
+        if let ty::Param(p) = base_t.kind {
+            self.defer_op_bound_error(err, p, missing_trait);
+        } else {
+            suggest_impl_missing(self.tcx, &mut err, base_t, &missing_trait);
+            err.emit();
+        }
+        self.tcx.types.err
+    }
+
+    /// Returns the name of the method of the operator trait `op` is overloaded with, and the
+    /// trait's `DefId` if the lang item is defined.
+    fn op_method_and_trait(&self, op: Op) -> (&'static str, Option<DefId>) {
+        let lang = self.tcx.lang_items();
+        if let Op::Binary(op, is_assign) = op {
+            let opname = op_method_name(op, is_assign);
+            let trait_did = match (op.node, is_assign) {
+                (hir::BinOpKind::Add, IsAssign::Yes) => lang.add_assign_trait(),
+                (hir::BinOpKind::Sub, IsAssign::Yes) => lang.sub_assign_trait(),
+                (hir::BinOpKind::Mul, IsAssign::Yes) => lang.mul_assign_trait(),
+                (hir::BinOpKind::Div, IsAssign::Yes) => lang.div_assign_trait(),
+                (hir::BinOpKind::Rem, IsAssign::Yes) => lang.rem_assign_trait(),
+                (hir::BinOpKind::BitXor, IsAssign::Yes) => lang.bitxor_assign_trait(),
+                (hir::BinOpKind::BitAnd, IsAssign::Yes) => lang.bitand_assign_trait(),
+                (hir::BinOpKind::BitOr, IsAssign::Yes) => lang.bitor_assign_trait(),
+                (hir::BinOpKind::Shl, IsAssign::Yes) => lang.shl_assign_trait(),
+                (hir::BinOpKind::Shr, IsAssign::Yes) => lang.shr_assign_trait(),
+                (hir::BinOpKind::Add, IsAssign::No) => lang.add_trait(),
+                (hir::BinOpKind::Sub, IsAssign::No) => lang.sub_trait(),
+                (hir::BinOpKind::Mul, IsAssign::No) => lang.mul_trait(),
+                (hir::BinOpKind::Div, IsAssign::No) => lang.div_trait(),
+                (hir::BinOpKind::Rem, IsAssign::No) => lang.rem_trait(),
+                (hir::BinOpKind::BitXor, IsAssign::No) => lang.bitxor_trait(),
+                (hir::BinOpKind::BitAnd, IsAssign::No) => lang.bitand_trait(),
+                (hir::BinOpKind::BitOr, IsAssign::No) => lang.bitor_trait(),
+                (hir::BinOpKind::Shl, IsAssign::No) => lang.shl_trait(),
+                (hir::BinOpKind::Shr, IsAssign::No) => lang.shr_trait(),
+                (hir::BinOpKind::Lt, IsAssign::No)
+                | (hir::BinOpKind::Le, IsAssign::No)
+                | (hir::BinOpKind::Ge, IsAssign::No)
+                | (hir::BinOpKind::Gt, IsAssign::No) => lang.partial_ord_trait(),
+                (hir::BinOpKind::Eq, IsAssign::No) | (hir::BinOpKind::Ne, IsAssign::No) => {
+                    lang.eq_trait()
+                }
+                // `op_method_name` has already rejected everything else.
+                _ => unreachable!(),
+            };
+            (opname, trait_did)
         } else if let Op::Unary(hir::UnOp::UnNot, _) = op {
             ("not", lang.not_trait())
         } else if let Op::Unary(hir::UnOp::UnNeg, _) = op {
             ("neg", lang.neg_trait())
         } else {
-            bug!("lookup_op_method: op not supported: {:?}", op)
+            bug!("op_method_and_trait: op not supported: {:?}", op)
+        }
+    }
+
+    /// Whether the operator `op` may be implemented for `lhs_ty` with `other_tys` as its other
+    /// operands. Unlike `lookup_op_method`, this doesn't register any obligations, so it can be
+    /// used to try out other operand types in diagnostics.
+    fn op_may_apply(&self, lhs_ty: Ty<'tcx>, other_tys: &[Ty<'tcx>], op: Op) -> bool {
+        let trait_did = match self.op_method_and_trait(op).1 {
+            Some(trait_did) => trait_did,
+            None => return false,
+        };
+        let other_tys = other_tys.iter().map(|&ty| GenericArg::from(ty)).collect::<Vec<_>>();
+        let trait_ref = ty::TraitRef::new(trait_did, self.tcx.mk_substs_trait(lhs_ty, &other_tys));
+        let obligation = traits::Obligation::new(
+            traits::ObligationCause::dummy(),
+            self.param_env,
+            trait_ref.to_poly_trait_ref().without_const().to_predicate(self.tcx),
+        );
+        self.predicate_may_hold(&obligation)
+    }
+
+    fn lookup_op_method(
+        &self,
+        lhs_ty: Ty<'tcx>,
+        other_tys: &[Ty<'tcx>],
+        op: Op,
+    ) -> Result<MethodCallee<'tcx>, ()> {
+        let span = match op {
+            Op::Binary(op, _) => op.span,
+            Op::Unary(_, span) => span,
         };
+        let (opname, trait_did) = self.op_method_and_trait(op);
 
         debug!(
-            "lookup_op_method(lhs_ty={:?}, op={:?}, opname={:?}, trait_did={:?})",
-            lhs_ty, op, opname, trait_did
+            "lookup_op_method(lhs_ty={:?}, other_tys={:?}, op={:?}, opname={:?}, trait_did={:?})",
+            lhs_ty, other_tys, op, opname, trait_did
         );
 
-        let method = trait_did.and_then(|trait_did| {
-            let opname = Ident::from_str(opname);
-            self.lookup_method_in_trait(span, opname, trait_did, lhs_ty, Some(other_tys))
-        });
+        let trait_did = match trait_did {
+            Some(trait_did) => trait_did,
+            None => {
+                debug!("lookup_op_method: no lang item for the `{}` operator trait", opname);
+                return Err(());
+            }
+        };
+        let method = self.lookup_method_in_trait(
+            span,
+            Ident::from_str(opname),
+            trait_did,
+            lhs_ty,
+            Some(other_tys),
+        );
 
         match method {
             Some(ok) => {
                 let method = self.register_infer_ok_obligations(ok);
                 self.select_obligations_where_possible(false, |_| {});
+                debug!(
+                    "lookup_op_method: resolved to {:?} with substs {:?}, sig={:?}",
+                    method.def_id, method.substs, method.sig
+                );
 
                 Ok(method)
             }
-            None => Err(()),
+            None => {
+                debug!(
+                    "lookup_op_method: `{}` isn't implemented for {:?} with {:?}",
+                    self.tcx.def_path_str(trait_did),
+                    self.resolve_vars_if_possible(&lhs_ty),
+                    self.resolve_vars_if_possible(&other_tys.to_vec()),
+                );
+                Err(())
+            }
         }
     }
 }
@@ -808,7 +4876,8 @@ enum BinOpCategory {
     Shortcircuit,
 
     /// <<, >> -- when shifting a single integer, rhs can be any
-    /// integer type. For simd, types must match.
+    /// integer type. For simd, lhs is an integer vector whose lanes
+    /// are all shifted by the same integer rhs.
     Shift,
 
     /// +, -, etc -- takes equal types, produces same type as input,
@@ -864,6 +4933,338 @@ enum Op {
     Unary(hir::UnOp, Span),
 }
 
+/// Returns the name of the trait method that the binary operator `op` desugars to, e.g. `add`
+/// for `a + b` and `add_assign` for `a += b`.
+fn op_method_name(op: hir::BinOp, is_assign: IsAssign) -> &'static str {
+    match is_assign {
+        IsAssign::Yes => match op.node {
+            hir::BinOpKind::Add => "add_assign",
+            hir::BinOpKind::Sub => "sub_assign",
+            hir::BinOpKind::Mul => "mul_assign",
+            hir::BinOpKind::Div => "div_assign",
+            hir::BinOpKind::Rem => "rem_assign",
+            hir::BinOpKind::BitXor => "bitxor_assign",
+            hir::BinOpKind::BitAnd => "bitand_assign",
+            hir::BinOpKind::BitOr => "bitor_assign",
+            hir::BinOpKind::Shl => "shl_assign",
+            hir::BinOpKind::Shr => "shr_assign",
+            hir::BinOpKind::Lt
+            | hir::BinOpKind::Le
+            | hir::BinOpKind::Ge
+            | hir::BinOpKind::Gt
+            | hir::BinOpKind::Eq
+            | hir::BinOpKind::Ne
+            | hir::BinOpKind::And
+            | hir::BinOpKind::Or => {
+                span_bug!(op.span, "impossible assignment operation: {}=", op.node.as_str())
+            }
+        },
+        IsAssign::No => match op.node {
+            hir::BinOpKind::Add => "add",
+            hir::BinOpKind::Sub => "sub",
+            hir::BinOpKind::Mul => "mul",
+            hir::BinOpKind::Div => "div",
+            hir::BinOpKind::Rem => "rem",
+            hir::BinOpKind::BitXor => "bitxor",
+            hir::BinOpKind::BitAnd => "bitand",
+            hir::BinOpKind::BitOr => "bitor",
+            hir::BinOpKind::Shl => "shl",
+            hir::BinOpKind::Shr => "shr",
+            hir::BinOpKind::Lt => "lt",
+            hir::BinOpKind::Le => "le",
+            hir::BinOpKind::Ge => "ge",
+            hir::BinOpKind::Gt => "gt",
+            hir::BinOpKind::Eq => "eq",
+            hir::BinOpKind::Ne => "ne",
+            hir::BinOpKind::And | hir::BinOpKind::Or => {
+                span_bug!(op.span, "&& and || are not overloadable")
+            }
+        },
+    }
+}
+
+/// Whether `a op b` and `b op a` have the same value for the builtin types, as they do for `+` but
+/// not for `-`. `&&` and `||` aren't, as they only evaluate their RHS depending on their LHS.
+fn op_is_commutative(op: hir::BinOpKind) -> bool {
+    match op {
+        hir::BinOpKind::Add
+        | hir::BinOpKind::Mul
+        | hir::BinOpKind::BitAnd
+        | hir::BinOpKind::BitOr
+        | hir::BinOpKind::BitXor
+        | hir::BinOpKind::Eq
+        | hir::BinOpKind::Ne => true,
+        hir::BinOpKind::Sub
+        | hir::BinOpKind::Div
+        | hir::BinOpKind::Rem
+        | hir::BinOpKind::Shl
+        | hir::BinOpKind::Shr
+        | hir::BinOpKind::Lt
+        | hir::BinOpKind::Le
+        | hir::BinOpKind::Gt
+        | hir::BinOpKind::Ge
+        | hir::BinOpKind::And
+        | hir::BinOpKind::Or => false,
+    }
+}
+
+/// Returns the outermost call of the function-like macro that wrote the code at `span`, like the
+/// `assert_eq!(a, b)` that compares `a` and `b`. Errors on an operation in a macro are reported
+/// there, as the definition of the macro can't be changed from the call. Derives aren't included,
+/// as their operations already point at the fields they compare.
+fn macro_call_site(span: Span) -> Option<Span> {
+    match span.ctxt().outer_expn_data().kind {
+        ExpnKind::Macro(MacroKind::Bang, _) => Some(span.source_callsite()),
+        _ => None,
+    }
+}
+
+/// Returns `snippet`, the source of `expr`, with parentheses if `expr` binds less tightly than an
+/// operator of precedence `prec`, e.g. `(a + b).to_owned()` for a method call with `PREC_POSTFIX`.
+fn parenthesize(expr: &hir::Expr<'_>, snippet: String, prec: i8) -> String {
+    if expr.precedence().order() < prec { format!("({})", snippet) } else { snippet }
+}
+
+/// Returns `snippet`, the source of `expr`, dereferenced, with parentheses if `expr` binds less
+/// tightly than the `*` operator, e.g. `*(x as &u8)` rather than `*x as &u8`.
+fn deref_snippet(expr: &hir::Expr<'_>, snippet: String) -> String {
+    format!("*{}", parenthesize(expr, snippet, PREC_PREFIX))
+}
+
+/// Operand types whose names are longer than this are shortened in operator errors.
+const LONG_OPERAND_TY_LEN: usize = 128;
+
+/// Shortens a type name by eliding generic arguments nested in other generic arguments, e.g.
+/// `Map<Filter<IntoIter<u8>, F>, G>` becomes `Map<Filter<...>, G>`.
+fn elide_nested_generic_args(ty_str: &str) -> String {
+    let mut shortened = String::with_capacity(ty_str.len());
+    let mut depth = 0usize;
+    let mut prev = None;
+    for c in ty_str.chars() {
+        match c {
+            '<' => {
+                depth += 1;
+                if depth <= 2 {
+                    shortened.push(c);
+                }
+                if depth == 2 {
+                    shortened.push_str("...");
+                }
+            }
+            // The `>` of a `->` in a function pointer type doesn't close generic arguments.
+            '>' if prev != Some('-') => {
+                if depth <= 2 {
+                    shortened.push(c);
+                }
+                depth = depth.saturating_sub(1);
+            }
+            _ if depth < 2 => shortened.push(c),
+            _ => {}
+        }
+        prev = Some(c);
+    }
+    shortened
+}
+
+/// Notes where the full names of the operand types shortened in an operator error were written.
+fn note_long_ty_paths(err: &mut DiagnosticBuilder<'_>, long_ty_paths: &[PathBuf]) {
+    for path in long_ty_paths {
+        err.note(&format!("the full type name has been written to '{}'", path.display()));
+    }
+}
+
+/// Returns the path of the trait that has to be implemented for the binary operator `op` to be
+/// applicable, e.g. `std::ops::Add` for `a + b` and `std::ops::AddAssign` for `a += b`.
+fn op_trait_path(op: hir::BinOp, is_assign: IsAssign) -> &'static str {
+    match is_assign {
+        IsAssign::Yes => match op.node {
+            hir::BinOpKind::Add => "std::ops::AddAssign",
+            hir::BinOpKind::Sub => "std::ops::SubAssign",
+            hir::BinOpKind::Mul => "std::ops::MulAssign",
+            hir::BinOpKind::Div => "std::ops::DivAssign",
+            hir::BinOpKind::Rem => "std::ops::RemAssign",
+            hir::BinOpKind::BitXor => "std::ops::BitXorAssign",
+            hir::BinOpKind::BitAnd => "std::ops::BitAndAssign",
+            hir::BinOpKind::BitOr => "std::ops::BitOrAssign",
+            hir::BinOpKind::Shl => "std::ops::ShlAssign",
+            hir::BinOpKind::Shr => "std::ops::ShrAssign",
+            hir::BinOpKind::Lt
+            | hir::BinOpKind::Le
+            | hir::BinOpKind::Ge
+            | hir::BinOpKind::Gt
+            | hir::BinOpKind::Eq
+            | hir::BinOpKind::Ne
+            | hir::BinOpKind::And
+            | hir::BinOpKind::Or => {
+                span_bug!(op.span, "impossible assignment operation: {}=", op.node.as_str())
+            }
+        },
+        IsAssign::No => match op.node {
+            hir::BinOpKind::Add => "std::ops::Add",
+            hir::BinOpKind::Sub => "std::ops::Sub",
+            hir::BinOpKind::Mul => "std::ops::Mul",
+            hir::BinOpKind::Div => "std::ops::Div",
+            hir::BinOpKind::Rem => "std::ops::Rem",
+            hir::BinOpKind::BitXor => "std::ops::BitXor",
+            hir::BinOpKind::BitAnd => "std::ops::BitAnd",
+            hir::BinOpKind::BitOr => "std::ops::BitOr",
+            hir::BinOpKind::Shl => "std::ops::Shl",
+            hir::BinOpKind::Shr => "std::ops::Shr",
+            hir::BinOpKind::Lt | hir::BinOpKind::Le | hir::BinOpKind::Ge | hir::BinOpKind::Gt => {
+                "std::cmp::PartialOrd"
+            }
+            hir::BinOpKind::Eq | hir::BinOpKind::Ne => "std::cmp::PartialEq",
+            hir::BinOpKind::And | hir::BinOpKind::Or => {
+                span_bug!(op.span, "&& and || are not overloadable")
+            }
+        },
+    }
+}
+
+/// Returns the value of `expr` if it is an integer literal.
+fn int_lit_value(expr: &hir::Expr<'_>) -> Option<u128> {
+    match expr.kind {
+        hir::ExprKind::Lit(ref lit) => match lit.node {
+            ast::LitKind::Int(value, _) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Computes the value of a binary operation on two integer literals of type `ty`. Returns `None`
+/// if the operands aren't literals or the operation overflows.
+fn const_op_eval<'tcx>(
+    tcx: TyCtxt<'tcx>,
+    op: hir::BinOp,
+    lhs_expr: &hir::Expr<'_>,
+    rhs_expr: &hir::Expr<'_>,
+    ty: Ty<'tcx>,
+) -> Option<String> {
+    let (lhs, rhs) = (int_lit_value(lhs_expr)?, int_lit_value(rhs_expr)?);
+    let pointer_bits = tcx.data_layout.pointer_size.bits();
+    match ty.kind {
+        ty::Int(ity) => {
+            let bits = ity.bit_width().unwrap_or(pointer_bits);
+            let (min, max) = (i128::MIN >> (128 - bits), i128::MAX >> (128 - bits));
+            let in_range = |value: i128| min <= value && value <= max;
+            let (lhs, rhs) = (i128::try_from(lhs).ok()?, i128::try_from(rhs).ok()?);
+            if !in_range(lhs) || !in_range(rhs) {
+                return None;
+            }
+            let value = match op.node {
+                hir::BinOpKind::Add => lhs.checked_add(rhs)?,
+                hir::BinOpKind::Sub => lhs.checked_sub(rhs)?,
+                hir::BinOpKind::Mul => lhs.checked_mul(rhs)?,
+                hir::BinOpKind::Div => lhs.checked_div(rhs)?,
+                hir::BinOpKind::Rem => lhs.checked_rem(rhs)?,
+                hir::BinOpKind::BitAnd => lhs & rhs,
+                hir::BinOpKind::BitOr => lhs | rhs,
+                hir::BinOpKind::BitXor => lhs ^ rhs,
+                _ => return None,
+            };
+            if in_range(value) { Some(value.to_string()) } else { None }
+        }
+        ty::Uint(uty) => {
+            let bits = uty.bit_width().unwrap_or(pointer_bits);
+            let max = u128::MAX >> (128 - bits);
+            if lhs > max || rhs > max {
+                return None;
+            }
+            let value = match op.node {
+                hir::BinOpKind::Add => lhs.checked_add(rhs)?,
+                hir::BinOpKind::Sub => lhs.checked_sub(rhs)?,
+                hir::BinOpKind::Mul => lhs.checked_mul(rhs)?,
+                hir::BinOpKind::Div => lhs.checked_div(rhs)?,
+                hir::BinOpKind::Rem => lhs.checked_rem(rhs)?,
+                hir::BinOpKind::BitAnd => lhs & rhs,
+                hir::BinOpKind::BitOr => lhs | rhs,
+                hir::BinOpKind::BitXor => lhs ^ rhs,
+                _ => return None,
+            };
+            if value <= max { Some(value.to_string()) } else { None }
+        }
+        _ => None,
+    }
+}
+
+/// Looks for function and method calls in an expression, outside of closures.
+struct CallFinder {
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for CallFinder {
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::Call(..) | hir::ExprKind::MethodCall(..) => self.found = true,
+            _ => intravisit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Looks for uses of a local variable in an expression, outside of closures.
+struct LocalUseFinder {
+    local: hir::HirId,
+    found: bool,
+}
+
+impl<'tcx> Visitor<'tcx> for LocalUseFinder {
+    type Map = intravisit::ErasedMap<'tcx>;
+
+    fn nested_visit_map(&mut self) -> NestedVisitorMap<Self::Map> {
+        NestedVisitorMap::None
+    }
+
+    fn visit_expr(&mut self, expr: &'tcx hir::Expr<'tcx>) {
+        match expr.kind {
+            hir::ExprKind::Path(hir::QPath::Resolved(None, path))
+                if path.res == Res::Local(self.local) =>
+            {
+                self.found = true
+            }
+            _ => intravisit::walk_expr(self, expr),
+        }
+    }
+}
+
+/// Whether `lhs` and `rhs` are written as the same place: the same local or static, or the same
+/// field of, or dereference of, such a place.
+fn is_same_place(lhs: &hir::Expr<'_>, rhs: &hir::Expr<'_>) -> bool {
+    match (&lhs.kind, &rhs.kind) {
+        (
+            hir::ExprKind::Path(hir::QPath::Resolved(None, lhs_path)),
+            hir::ExprKind::Path(hir::QPath::Resolved(None, rhs_path)),
+        ) => {
+            lhs_path.res == rhs_path.res
+                && matches!(lhs_path.res, Res::Local(_) | Res::Def(DefKind::Static, _))
+        }
+        (hir::ExprKind::Field(lhs, lhs_ident), hir::ExprKind::Field(rhs, rhs_ident)) => {
+            lhs_ident.name == rhs_ident.name && is_same_place(lhs, rhs)
+        }
+        (
+            hir::ExprKind::Unary(hir::UnOp::UnDeref, lhs),
+            hir::ExprKind::Unary(hir::UnOp::UnDeref, rhs),
+        ) => is_same_place(lhs, rhs),
+        _ => false,
+    }
+}
+
+/// Returns the sign and magnitude of `expr` if it is an integer literal, possibly negated.
+fn shift_amount(expr: &hir::Expr<'_>) -> Option<(bool, u128)> {
+    match expr.kind {
+        hir::ExprKind::Unary(hir::UnOp::UnNeg, ref inner) => {
+            int_lit_value(inner).map(|value| (true, value))
+        }
+        _ => int_lit_value(expr).map(|value| (false, value)),
+    }
+}
+
 /// Dereferences a single level of immutable referencing.
 fn deref_ty_if_possible(ty: Ty<'tcx>) -> Ty<'tcx> {
     match ty.kind {
@@ -888,7 +5289,7 @@ fn deref_ty_if_possible(ty: Ty<'tcx>) -> Ty<'tcx> {
 /// Reason #2 is the killer. I tried for a while to always use
 /// overloaded logic and just check the types in constants/codegen after
 /// the fact, and it worked fine, except for SIMD types. -nmatsakis
-fn is_builtin_binop<'tcx>(lhs: Ty<'tcx>, rhs: Ty<'tcx>, op: hir::BinOp) -> bool {
+fn is_builtin_binop<'tcx>(tcx: TyCtxt<'tcx>, lhs: Ty<'tcx>, rhs: Ty<'tcx>, op: hir::BinOp) -> bool {
     // Special-case a single layer of referencing, so that things like `5.0 + &6.0f32` work.
     // (See https://github.com/rust-lang/rust/issues/57447.)
     let (lhs, rhs) = (deref_ty_if_possible(lhs), deref_ty_if_possible(rhs));
@@ -900,6 +5301,9 @@ fn is_builtin_binop<'tcx>(lhs: Ty<'tcx>, rhs: Ty<'tcx>, op: hir::BinOp) -> bool
             lhs.references_error()
                 || rhs.references_error()
                 || lhs.is_integral() && rhs.is_integral()
+                || tcx.features().simd_shift_by_scalar
+                    && is_integer_vector(tcx, lhs)
+                    && rhs.is_integral()
         }
 
         BinOpCategory::Math => {
@@ -923,8 +5327,30 @@ fn is_builtin_binop<'tcx>(lhs: Ty<'tcx>, rhs: Ty<'tcx>, op: hir::BinOp) -> bool
     }
 }
 
-/// If applicable, note that an implementation of `trait` for `ty` may fix the error.
-fn suggest_impl_missing(err: &mut DiagnosticBuilder<'_>, ty: Ty<'_>, missing_trait: &str) {
+/// Returns the number of lanes and the element type of a SIMD vector.
+fn simd_lanes<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> Option<(u64, Ty<'tcx>)> {
+    match ty.kind {
+        // `#[repr(simd)]` on a struct without fields is an error reported elsewhere.
+        Adt(def, _) if ty.is_simd() && !def.non_enum_variant().fields.is_empty() => {
+            Some(ty.simd_size_and_type(tcx))
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether `ty` is a SIMD vector of integers.
+fn is_integer_vector<'tcx>(tcx: TyCtxt<'tcx>, ty: Ty<'tcx>) -> bool {
+    simd_lanes(tcx, ty).map_or(false, |(_, elem)| elem.is_integral())
+}
+
+/// If applicable, note that an implementation of `trait` for `ty` may fix the error. For a generic
+/// type, also show how the header of such an implementation could look.
+fn suggest_impl_missing(
+    tcx: TyCtxt<'_>,
+    err: &mut DiagnosticBuilder<'_>,
+    ty: Ty<'_>,
+    missing_trait: &str,
+) {
     if let Adt(def, _) = ty.peel_refs().kind {
         if def.did.is_local() {
             err.note(&format!(
@@ -932,46 +5358,121 @@ fn suggest_impl_missing(err: &mut DiagnosticBuilder<'_>, ty: Ty<'_>, missing_tra
                 be missing for `{}`",
                 missing_trait, ty
             ));
+            if let Some(header) = generic_impl_header(tcx, def.did, missing_trait) {
+                err.help(&format!("an implementation could start with `{}`", header));
+            }
+        }
+    }
+}
+
+/// Returns the header of an implementation of `trait_path` for the generic ADT `def_id`, whose
+/// type parameters are bound by the trait, e.g. `impl<T: PartialEq> PartialEq for Wrapper<T>`.
+fn generic_impl_header(tcx: TyCtxt<'_>, def_id: DefId, trait_path: &str) -> Option<String> {
+    let item = match tcx.hir().get_if_local(def_id)? {
+        hir::Node::Item(item) => item,
+        _ => return None,
+    };
+    let generics = match item.kind {
+        hir::ItemKind::Struct(_, ref generics)
+        | hir::ItemKind::Enum(_, ref generics)
+        | hir::ItemKind::Union(_, ref generics) => generics,
+        _ => return None,
+    };
+    if generics.params.is_empty() {
+        return None;
+    }
+    let source_map = tcx.sess.source_map();
+    let mut params = vec![];
+    let mut args = vec![];
+    for param in generics.params {
+        let name = param.name.ident().to_string();
+        match param.kind {
+            hir::GenericParamKind::Lifetime { .. } => params.push(name.clone()),
+            hir::GenericParamKind::Type { .. } => {
+                params.push(format!("{}: {}", name, trait_path));
+            }
+            hir::GenericParamKind::Const { ref ty } => {
+                let ty = source_map.span_to_snippet(ty.span).ok()?;
+                params.push(format!("const {}: {}", name, ty));
+            }
+        }
+        args.push(name);
+    }
+    Some(format!(
+        "impl<{}> {} for {}<{}>",
+        params.join(", "),
+        trait_path,
+        item.ident,
+        args.join(", ")
+    ))
+}
+
+/// Returns the default of the type parameter `p` of the item `def_id`. Impls can't give their
+/// parameters defaults, so for a parameter of an impl this is the default of the corresponding
+/// parameter of the type the impl is for, if the parameter is passed to it directly.
+fn type_param_default<'tcx>(tcx: TyCtxt<'tcx>, def_id: DefId, p: ty::ParamTy) -> Option<Ty<'tcx>> {
+    let default_of = |param: &ty::GenericParamDef| match param.kind {
+        ty::GenericParamDefKind::Type { has_default: true, .. } => Some(tcx.type_of(param.def_id)),
+        _ => None,
+    };
+    let param = tcx.generics_of(def_id).type_param(&p, tcx);
+    if let Some(default_ty) = default_of(param) {
+        return Some(default_ty);
+    }
+    let owner = tcx.parent(param.def_id)?;
+    if tcx.def_kind(owner) != DefKind::Impl {
+        return None;
+    }
+    match tcx.type_of(owner).kind {
+        ty::Adt(def, substs) => {
+            let index = substs.iter().position(|arg| arg == p.to_ty(tcx).into())?;
+            default_of(tcx.generics_of(def.did).param_at(index, tcx))
         }
+        _ => None,
     }
 }
 
-fn suggest_constraining_param(
+/// Suggests restricting each type param in `params` with the operator trait bound it's missing.
+/// The params declared by the same item are all restricted by a single suggestion.
+fn suggest_constraining_params(
     tcx: TyCtxt<'_>,
     body_id: hir::HirId,
-    mut err: &mut DiagnosticBuilder<'_>,
-    lhs_ty: Ty<'_>,
-    rhs_ty: Ty<'_>,
-    missing_trait: &str,
-    p: ty::ParamTy,
-    set_output: bool,
+    err: &mut Diagnostic,
+    params: &[(ty::ParamTy, String)],
 ) {
     let hir = tcx.hir();
-    let msg = &format!("`{}` might need a bound for `{}`", lhs_ty, missing_trait);
-    // Try to find the def-id and details for the parameter p. We have only the index,
+    // Try to find the def-id and details for the parameters. We have only the indices,
     // so we have to find the enclosing function's def-id, then look through its declared
-    // generic parameters to get the declaration.
+    // generic parameters to get the declarations.
     let def_id = hir.body_owner_def_id(hir::BodyId { hir_id: body_id });
     let generics = tcx.generics_of(def_id);
-    let param_def_id = generics.type_param(&p, tcx).def_id;
-    if let Some(generics) = param_def_id
-        .as_local()
-        .map(|id| hir.as_local_hir_id(id))
-        .and_then(|id| hir.find(hir.get_parent_item(id)))
-        .as_ref()
-        .and_then(|node| node.generics())
-    {
-        let output = if set_output { format!("<Output = {}>", rhs_ty) } else { String::new() };
-        suggest_constraining_type_param(
-            tcx,
-            generics,
-            &mut err,
-            &format!("{}", lhs_ty),
-            &format!("{}{}", missing_trait, output),
-            None,
-        );
-    } else {
-        let span = tcx.def_span(param_def_id);
-        err.span_label(span, msg);
+    let mut by_owner: Vec<(hir::HirId, hir::Node<'_>, Vec<(String, &str)>)> = vec![];
+    for (p, constraint) in params {
+        let param_def_id = generics.type_param(p, tcx).def_id;
+        let owner = param_def_id
+            .as_local()
+            .map(|id| hir.get_parent_item(hir.as_local_hir_id(id)))
+            .and_then(|owner| Some((owner, hir.find(owner)?)))
+            .filter(|(_, node)| node.generics().is_some());
+        let constraint = (p.to_string(), constraint.as_str());
+        match owner {
+            Some((owner, node)) => match by_owner.iter_mut().find(|(other, ..)| *other == owner) {
+                Some((.., constraints)) => constraints.push(constraint),
+                None => by_owner.push((owner, node, vec![constraint])),
+            },
+            None => {
+                let span = tcx.def_span(param_def_id);
+                err.span_label(span, format!("`{}` might need a bound for `{}`", p, constraint.1));
+            }
+        }
+    }
+    for (_, node, constraints) in &by_owner {
+        if let Some(owner_generics) = node.generics() {
+            suggest_constraining_type_params(
+                owner_generics,
+                err,
+                constraints.iter().map(|(name, constraint)| (name.as_str(), *constraint)),
+            );
+        }
     }
 }
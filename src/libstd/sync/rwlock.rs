@@ -64,6 +64,7 @@ use crate::sys_common::rwlock as sys;
 /// [`Sync`]: ../../std/marker/trait.Sync.html
 /// [`Mutex`]: struct.Mutex.html
 #[stable(feature = "rust1", since = "1.0.0")]
+#[cfg_attr(not(test), rustc_diagnostic_item = "rwlock_type")]
 pub struct RwLock<T: ?Sized> {
     inner: Box<sys::RWLock>,
     poison: poison::Flag,
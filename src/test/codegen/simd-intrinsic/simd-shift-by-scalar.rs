@@ -0,0 +1,40 @@
+// ignore-tidy-linelength
+
+// compile-flags: -C no-prepopulate-passes
+
+#![crate_type = "lib"]
+
+#![feature(repr_simd, simd_shift_by_scalar)]
+#![allow(non_camel_case_types)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+pub struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+// Shifting a vector by a scalar amount masks the amount and splats it across the lanes.
+
+// CHECK-LABEL: @shl_u32x4
+#[no_mangle]
+pub fn shl_u32x4(x: u32x4, n: u32) -> u32x4 {
+    // CHECK: [[MASKED:%.*]] = and i32 %n, 31
+    // CHECK: [[INSERTED:%.*]] = insertelement <4 x i32> undef, i32 [[MASKED]], i32 0
+    // CHECK: [[SPLAT:%.*]] = shufflevector <4 x i32> [[INSERTED]], <4 x i32> undef, <4 x i32> zeroinitializer
+    // CHECK: shl <4 x i32> {{.*}}, [[SPLAT]]
+    x << n
+}
+
+// CHECK-LABEL: @shr_assign_i16x8
+#[no_mangle]
+pub fn shr_assign_i16x8(mut x: i16x8, n: u32) -> i16x8 {
+    // CHECK: [[TRUNCATED:%.*]] = trunc i32 %n to i16
+    // CHECK: [[MASKED:%.*]] = and i16 [[TRUNCATED]], 15
+    // CHECK: [[INSERTED:%.*]] = insertelement <8 x i16> undef, i16 [[MASKED]], i32 0
+    // CHECK: [[SPLAT:%.*]] = shufflevector <8 x i16> [[INSERTED]], <8 x i16> undef, <8 x i32> zeroinitializer
+    // CHECK: ashr <8 x i16> {{.*}}, [[SPLAT]]
+    x >>= n;
+    x
+}
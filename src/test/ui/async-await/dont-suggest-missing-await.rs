@@ -16,4 +16,9 @@ async fn dont_suggest_await_in_closure() {
     };
 }
 
+fn dont_suggest_await_on_operand_in_fn(x: u32) -> u32 {
+    x + make_u32()
+    //~^ ERROR cannot add `impl std::future::Future` to `u32` [E0369]
+}
+
 fn main() {}
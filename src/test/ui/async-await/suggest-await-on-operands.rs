@@ -0,0 +1,31 @@
+// edition:2018
+// run-rustfix
+
+#![allow(unused)]
+
+async fn number() -> u32 {
+    22
+}
+
+async fn add_future(x: u32) -> u32 {
+    x + number()
+    //~^ ERROR cannot add `impl std::future::Future` to `u32` [E0369]
+    //~| HELP consider using `.await` here
+}
+
+async fn add_to_future(x: u32) -> u32 {
+    let fut = number();
+    fut * x
+    //~^ ERROR cannot multiply `u32` to `impl std::future::Future` [E0369]
+    //~| HELP consider using `.await` here
+}
+
+async fn add_assign_future() -> u32 {
+    let mut total = 0u32;
+    total += number();
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `u32` [E0368]
+    //~| HELP consider using `.await` here
+    total
+}
+
+fn main() {}
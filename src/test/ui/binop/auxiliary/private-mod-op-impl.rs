@@ -0,0 +1,14 @@
+pub struct Meters(pub u32);
+
+mod ops {
+    use std::ops::Add;
+
+    // The impl is usable wherever `Meters` is, even though this module is private.
+    impl Add for super::Meters {
+        type Output = super::Meters;
+
+        fn add(self, rhs: super::Meters) -> super::Meters {
+            super::Meters(self.0 + rhs.0)
+        }
+    }
+}
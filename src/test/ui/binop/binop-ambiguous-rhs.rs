@@ -0,0 +1,42 @@
+// When the left-hand side implements an operator for several right-hand side types, an unknown
+// right-hand side type is explained, since the `Output` of the operator depends on it.
+
+use std::ops::Add;
+
+struct Distance(f64);
+struct Meters(f64);
+struct Feet(f64);
+
+impl From<f64> for Meters {
+    fn from(value: f64) -> Meters {
+        Meters(value)
+    }
+}
+
+impl From<f64> for Feet {
+    fn from(value: f64) -> Feet {
+        Feet(value)
+    }
+}
+
+impl Add<Meters> for Distance {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+impl Add<Feet> for Distance {
+    type Output = Feet;
+
+    fn add(self, other: Feet) -> Feet {
+        Feet(self.0 + other.0)
+    }
+}
+
+fn main() {
+    let d = Distance(1.0);
+    let x: f64 = 2.0;
+    let _total = d + x.into(); //~ ERROR type annotations needed
+}
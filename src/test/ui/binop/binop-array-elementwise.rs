@@ -0,0 +1,24 @@
+// Arithmetic on two arrays or slices of numbers explains that it doesn't apply element-wise.
+
+fn add_literals() {
+    let _ = [1, 2, 3] + [4, 5, 6];
+    //~^ ERROR cannot add `[{integer}; 3]` to `[{integer}; 3]`
+}
+
+fn mul_simd_sized(a: [f32; 4], b: [f32; 4]) {
+    let _ = a * b;
+    //~^ ERROR cannot multiply `[f32; 4]` to `[f32; 4]`
+}
+
+fn sub_slices(a: &[i32], b: &[i32]) {
+    let _ = a - b;
+    //~^ ERROR cannot subtract `&[i32]` from `&[i32]`
+}
+
+// No note: the element types differ.
+fn add_mismatched(a: [u8; 2], b: [u16; 2]) {
+    let _ = a + b;
+    //~^ ERROR cannot add `[u16; 2]` to `[u8; 2]`
+}
+
+fn main() {}
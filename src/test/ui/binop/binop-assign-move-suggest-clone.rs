@@ -0,0 +1,33 @@
+// Moving the right-hand side of an overloaded compound assignment suggests
+// cloning it when the value is used afterwards and implements `Clone`.
+
+use std::ops::AddAssign;
+
+#[derive(Clone)]
+struct Meters(u32);
+
+impl AddAssign for Meters {
+    fn add_assign(&mut self, other: Meters) {
+        self.0 += other.0;
+    }
+}
+
+struct Feet(u32);
+
+impl AddAssign for Feet {
+    fn add_assign(&mut self, other: Feet) {
+        self.0 += other.0;
+    }
+}
+
+fn add_meters(a: &mut Meters, b: Meters) {
+    *a += b;
+    drop(b); //~ ERROR use of moved value: `b`
+}
+
+fn add_feet(a: &mut Feet, b: Feet) {
+    *a += b;
+    drop(b); //~ ERROR use of moved value: `b`
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+// Compound assignments to `const` items explain that constants can't be mutated.
+
+const LIMIT: u32 = 10;
+
+struct Counter;
+
+impl Counter {
+    const STEP: u32 = 1;
+
+    fn bump() {
+        Self::STEP += 1; //~ ERROR constants cannot be mutated
+    }
+}
+
+fn main() {
+    LIMIT += 5; //~ ERROR constants cannot be mutated
+    Counter::bump();
+}
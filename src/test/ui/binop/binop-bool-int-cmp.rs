@@ -0,0 +1,11 @@
+// Comparing a `bool` with an integer literal suggests comparing it with `true` or `false`, or
+// converting it to an integer.
+
+fn main() {
+    let v: Vec<u8> = Vec::new();
+    let opt = Some(1);
+    let flag = true;
+    let _ = v.is_empty() == 0; //~ ERROR mismatched types
+    let _ = opt.is_some() != 1u8; //~ ERROR mismatched types
+    let _ = !flag == 2; //~ ERROR mismatched types
+}
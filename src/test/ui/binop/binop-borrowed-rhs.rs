@@ -0,0 +1,31 @@
+// run-rustfix
+// Operators that are only implemented for a borrowed RHS suggest borrowing it, even when the
+// RHS can't be coerced because there are several implementations.
+
+use std::ops::Sub;
+
+#[derive(Clone, Copy)]
+struct Meters(f64);
+
+impl Sub<&Meters> for Meters {
+    type Output = Meters;
+    fn sub(self, other: &Meters) -> Meters {
+        Meters(self.0 - other.0)
+    }
+}
+
+impl Sub<&f64> for Meters {
+    type Output = Meters;
+    fn sub(self, other: &f64) -> Meters {
+        Meters(self.0 - other)
+    }
+}
+
+fn main() {
+    let a = Meters(3.0);
+    let b = Meters(1.0);
+    let _ = a - b;
+    //~^ ERROR cannot subtract `Meters` from `Meters`
+    let _ = a - 1.5 * 2.0;
+    //~^ ERROR cannot subtract `{float}` from `Meters`
+}
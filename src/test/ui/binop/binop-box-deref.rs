@@ -0,0 +1,19 @@
+// run-rustfix
+// Operators applied to boxed numbers suggest dereferencing the boxes.
+
+fn main() {
+    let a = Box::new(1i32);
+    let b = Box::new(2i32);
+    let _ = a + b;
+    //~^ ERROR cannot add `std::boxed::Box<i32>` to `std::boxed::Box<i32>`
+    let _ = a * 3;
+    //~^ ERROR cannot multiply `{integer}` to `std::boxed::Box<i32>`
+    let c = 1.5f64;
+    let _ = c - Box::new(0.5f64);
+    //~^ ERROR cannot subtract `std::boxed::Box<f64>` from `f64`
+    let _ = a == 1;
+    //~^ ERROR binary operation `==` cannot be applied to type `std::boxed::Box<i32>`
+    let mut d = Box::new(5u8);
+    d += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::boxed::Box<u8>`
+}
@@ -0,0 +1,25 @@
+// Operators on `Cell` operands suggest reading their contents with `get`.
+
+use std::cell::Cell;
+
+fn scale(threshold: u32, cell_value: Cell<u32>) -> u32 {
+    threshold * cell_value
+    //~^ ERROR cannot multiply `std::cell::Cell<u32>` to `u32`
+}
+
+fn next(cell: Cell<i32>) -> i32 {
+    cell + 1
+    //~^ ERROR cannot add `{integer}` to `std::cell::Cell<i32>`
+}
+
+fn negate(cell: Cell<i32>) -> i32 {
+    -cell
+    //~^ ERROR cannot apply unary operator `-` to type `std::cell::Cell<i32>`
+}
+
+fn greet(name: Cell<String>) -> String {
+    name + "!"
+    //~^ ERROR cannot add `&str` to `std::cell::Cell<std::string::String>`
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+// Arithmetic on a `char` explains how to go through its code point instead.
+
+fn main() {
+    let c = 'a';
+    let _ = c + 1; //~ ERROR cannot add `{integer}` to `char`
+    let _ = 'z' - 2u32; //~ ERROR cannot subtract `u32` from `char`
+    let _ = c - 'a'; //~ ERROR cannot subtract `char` from `char`
+    let _ = c * 2; //~ ERROR cannot multiply `{integer}` to `char`
+    let mut d = 'b';
+    d += 1; //~ ERROR binary assignment operation `+=` cannot be applied to type `char`
+}
@@ -0,0 +1,13 @@
+// run-rustfix
+// Comparing a `cmp::Reverse` with the type it wraps suggests comparing its contents with `.0`.
+
+use std::cmp::Reverse;
+
+fn main() {
+    let (cost, limit) = (3u64, 5u64);
+    let key = Reverse(cost);
+    let _ = key < limit; //~ ERROR mismatched types
+    let _ = limit >= key; //~ ERROR mismatched types
+    let _ = Reverse(cost) > limit; //~ ERROR mismatched types
+    let _ = key == 3; //~ ERROR mismatched types
+}
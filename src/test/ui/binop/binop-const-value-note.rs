@@ -0,0 +1,17 @@
+// The value of a binary operation on two integer literals is noted when its type is unexpected.
+
+fn take_u64(_: u64) {}
+fn take_i8(_: i8) {}
+
+fn main() {
+    take_u64(3u32 + 4u32);
+    //~^ ERROR mismatched types
+    //~| NOTE the value of this expression is `7u32`
+    take_i8(100i16 - 7i16);
+    //~^ ERROR mismatched types
+    //~| NOTE the value of this expression is `93i16`
+    take_i8(6i16 * -2);
+    //~^ ERROR mismatched types
+    take_u64(255u8 + 1u8);
+    //~^ ERROR mismatched types
+}
@@ -0,0 +1,29 @@
+// run-rustfix
+// The suggestion to dereference the LHS of an operator adds parentheses where they're needed.
+
+struct Counter<'a> {
+    count: &'a mut i32,
+    parity: &'a &'a i32,
+}
+
+fn parity<'a>(x: &'a &'a i32) -> &'a &'a i32 {
+    x
+}
+
+fn main() {
+    let mut n = 1;
+    let m = &3;
+    let c = Counter { count: &mut n, parity: &m };
+    c.count += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `&mut i32`
+    let mut k = 2;
+    let mut v = vec![&mut k];
+    v[0] += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `&mut i32`
+    let _ = c.parity % 2;
+    //~^ ERROR cannot mod `&&i32` by `{integer}`
+    let _ = parity(&m) % 2;
+    //~^ ERROR cannot mod `&&i32` by `{integer}`
+    let _ = &m as &&i32 % 2;
+    //~^ ERROR cannot mod `&&i32` by `{integer}`
+}
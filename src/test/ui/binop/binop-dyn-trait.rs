@@ -0,0 +1,24 @@
+// Operators on trait objects explain that operator traits can't be added to `dyn Trait` as
+// extra bounds, and that they can be implemented for pointers to a local trait object.
+
+trait Shape {
+    fn area(&self) -> f64;
+}
+
+fn combine(a: Box<dyn Shape>, b: Box<dyn Shape>) {
+    let _ = a + b; //~ ERROR cannot add
+}
+
+fn same(a: Box<dyn Shape>, b: Box<dyn Shape>) -> bool {
+    a == b //~ ERROR binary operation `==` cannot be applied
+}
+
+fn scale(a: &dyn Shape, b: &dyn Shape) {
+    let _ = a * b; //~ ERROR cannot multiply `&dyn Shape` to `&dyn Shape`
+}
+
+fn grow(a: &mut dyn Shape, b: f64) {
+    *a *= b; //~ ERROR binary assignment operation `*=` cannot be applied to type `dyn Shape`
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+// check-pass
+// aux-build:private-mod-op-impl.rs
+
+// Operator impls have no visibility of their own: an impl in a private module of another crate
+// can be used wherever both the type and the trait can be named.
+
+extern crate private_mod_op_impl;
+
+use private_mod_op_impl::Meters;
+
+fn main() {
+    let Meters(total) = Meters(1) + Meters(2);
+    assert_eq!(total, 3);
+}
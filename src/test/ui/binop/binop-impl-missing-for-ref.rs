@@ -0,0 +1,56 @@
+// An operator applied to a reference to a type that implements the operator suggests an
+// implementation for the reference that delegates to the one for the type.
+
+use std::ops::{Add, Mul, Sub};
+
+#[derive(Clone)]
+struct Meters(f64);
+
+impl Add for Meters {
+    type Output = Meters;
+    fn add(self, rhs: Meters) -> Meters {
+        Meters(self.0 + rhs.0)
+    }
+}
+
+impl Mul<f64> for Meters {
+    type Output = Meters;
+    fn mul(self, rhs: f64) -> Meters {
+        Meters(self.0 * rhs)
+    }
+}
+
+fn main() {
+    let a = Meters(1.0);
+    let b = Meters(2.0);
+    let _ = &a + &b;
+    //~^ ERROR cannot add `&Meters` to `&Meters`
+    let _ = &a + b.clone();
+    //~^ ERROR cannot add `Meters` to `&Meters`
+    let _ = &a * 2.0f64;
+    //~^ ERROR cannot multiply `f64` to `&Meters`
+    let _ = &Seconds(2.0) - &Seconds(1.0);
+    //~^ ERROR cannot subtract `&Seconds` from `&Seconds`
+    let _ = &Grams(1.0) + &Grams(2.0);
+    //~^ ERROR cannot add `&Grams` to `&Grams`
+}
+
+#[derive(Clone, Copy)]
+struct Seconds(f64);
+
+impl Sub for Seconds {
+    type Output = Seconds;
+    fn sub(self, rhs: Seconds) -> Seconds {
+        Seconds(self.0 - rhs.0)
+    }
+}
+
+// Neither `Copy` nor `Clone`, so an implementation for `&Grams` can't delegate to this one.
+struct Grams(f64);
+
+impl Add for Grams {
+    type Output = Grams;
+    fn add(self, rhs: Grams) -> Grams {
+        Grams(self.0 + rhs.0)
+    }
+}
@@ -0,0 +1,16 @@
+// The note about a missing operator impl for a generic type shows a header for such an impl.
+
+struct Wrapper<T>(T);
+
+struct Borrowed<'a, T, U>(&'a T, U);
+
+fn main() {
+    let (a, b) = (Wrapper(1u8), Wrapper(2u8));
+    let _ = a == b;
+    //~^ ERROR binary operation `==` cannot be applied to type `Wrapper<u8>`
+
+    let x = 1u8;
+    let c = Borrowed(&x, 'c');
+    let _ = -c;
+    //~^ ERROR cannot apply unary operator `-` to type `Borrowed<'_, u8, char>`
+}
@@ -0,0 +1,26 @@
+// check-pass
+
+// The operator bounds of an `impl Trait` return type are enough to apply the operator to the
+// returned value, and the RHS is inferred from the bound.
+
+use std::ops::{Add, AddAssign, Neg};
+
+fn offset() -> impl Add<i64, Output = i64> {
+    5i64
+}
+
+fn counter() -> impl AddAssign<u8> + Copy {
+    0u8
+}
+
+fn flipped() -> impl Neg<Output = f32> {
+    1.0f32
+}
+
+fn main() {
+    let total: i64 = offset() + 1;
+    let mut count = counter();
+    count += 1;
+    let negated: f32 = -flipped();
+    let _ = (total, count, negated);
+}
@@ -0,0 +1,22 @@
+// Operator errors inside macros point at the macro call, keeping only the labels and suggestions
+// on the arguments of the macro.
+
+#[derive(Debug)]
+struct Meters(u32);
+
+macro_rules! add {
+    ($a:expr, $b:expr) => {
+        $a + $b
+    };
+}
+
+fn main() {
+    assert_eq!(Meters(1), Meters(1));
+    //~^ ERROR binary operation `==` cannot be applied to type `Meters`
+    let _ = add!(Meters(1), 2u32);
+    //~^ ERROR cannot add `u32` to `Meters`
+    // No suggestion replaces the addition, as it was written by the macro.
+    let v = vec![1];
+    let _ = add!(v.clone(), v);
+    //~^ ERROR cannot add `std::vec::Vec<{integer}>` to `std::vec::Vec<{integer}>`
+}
@@ -0,0 +1,22 @@
+// Adding two iterators suggests chaining them.
+
+fn evens() -> impl Iterator<Item = u32> {
+    (0..10).filter(|x| x % 2 == 0)
+}
+
+fn main() {
+    let a = 0..3u32;
+    let b = 5..8u32;
+    let _ = a + b;
+    //~^ ERROR cannot add `std::ops::Range<u32>` to `std::ops::Range<u32>`
+    let _ = evens() + evens();
+    //~^ ERROR cannot add `impl std::iter::Iterator` to `impl std::iter::Iterator`
+    let v = vec![1i32, 2];
+    let _ = v.iter() + v.iter();
+    //~^ ERROR cannot add `std::slice::Iter<'_, i32>` to `std::slice::Iter<'_, i32>`
+
+    // No suggestion: the right-hand side is not an iterator.
+    let c = 0..3u32;
+    let _ = c + 1u32;
+    //~^ ERROR cannot add `u32` to `std::ops::Range<u32>`
+}
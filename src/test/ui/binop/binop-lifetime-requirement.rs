@@ -0,0 +1,20 @@
+// Operator impls are selected without regard to lifetimes, so an impl whose lifetimes don't fit
+// the operands is still used, and borrowck reports the lifetime requirement on the operand.
+
+use std::ops::Add;
+
+struct Meters(u32);
+
+impl Add<&'static Meters> for Meters {
+    type Output = Meters;
+
+    fn add(self, rhs: &'static Meters) -> Meters {
+        Meters(self.0 + rhs.0)
+    }
+}
+
+fn main() {
+    let x = Meters(1);
+    let _ = Meters(2) + &x;
+    //~^ ERROR `x` does not live long enough
+}
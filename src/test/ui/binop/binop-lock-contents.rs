@@ -0,0 +1,26 @@
+// Operators on `Mutex`, `RwLock` and `RefCell` operands suggest locking or borrowing them.
+
+use std::cell::RefCell;
+use std::sync::{Mutex, RwLock};
+
+fn total(total: u32, job_count: Mutex<u32>) -> u32 {
+    total + job_count
+    //~^ ERROR cannot add `std::sync::Mutex<u32>` to `u32`
+}
+
+fn count(count: Mutex<u32>) {
+    count += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::sync::Mutex<u32>`
+}
+
+fn same(limit: RwLock<u8>, current: RwLock<u8>) -> bool {
+    limit == current
+    //~^ ERROR binary operation `==` cannot be applied to type `std::sync::RwLock<u8>`
+}
+
+fn grow(cell: RefCell<i32>) {
+    cell += 2;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::cell::RefCell<i32>`
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Operand types with overly long names are shortened in operator errors.
+// normalize-stderr-test "long-type-\d+" -> "long-type-hash"
+
+type A = Option<(u8, u8, u8)>;
+type B = Option<(A, A, A)>;
+
+fn main() {
+    let a = std::iter::empty::<B>();
+    let b = std::iter::empty::<B>();
+    let _ = a == b;
+    //~^ ERROR binary operation `==` cannot be applied to type `std::iter::Empty<
+}
@@ -0,0 +1,15 @@
+// run-rustfix
+// Operators on a `ManuallyDrop` of a `Copy` type suggest dereferencing it.
+
+use std::mem::ManuallyDrop;
+
+fn main() {
+    let mut md_value = ManuallyDrop::new(1u64);
+    let _ = md_value + 1;
+    //~^ ERROR cannot add `{integer}` to `std::mem::ManuallyDrop<u64>`
+    md_value += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type
+    let flag = ManuallyDrop::new(true);
+    let _ = !flag;
+    //~^ ERROR cannot apply unary operator `!` to type `std::mem::ManuallyDrop<bool>`
+}
@@ -0,0 +1,21 @@
+// run-rustfix
+// Maps can't be added, suggest extending one with the other instead.
+
+use std::collections::{BTreeMap, HashMap};
+
+fn make() -> HashMap<u32, char> {
+    HashMap::new()
+}
+
+fn main() {
+    let mut a: HashMap<u32, char> = HashMap::new();
+    let b: HashMap<u32, char> = HashMap::new();
+    a += b;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type
+    a + make();
+    //~^ ERROR cannot add `std::collections::HashMap<u32, char>` to
+    let c: BTreeMap<u8, String> = BTreeMap::new();
+    let d = c.clone();
+    let _e = c + d;
+    //~^ ERROR cannot add `std::collections::BTreeMap<u8, std::string::String>` to
+}
@@ -0,0 +1,20 @@
+// Operators on `MaybeUninit` operands explain that the value has to be extracted first.
+
+use std::mem::MaybeUninit;
+
+fn total(buf_len: MaybeUninit<usize>, extra: usize) -> usize {
+    buf_len + extra
+    //~^ ERROR cannot add `usize` to `std::mem::MaybeUninit<usize>`
+}
+
+fn same(a: MaybeUninit<u8>, b: MaybeUninit<u8>) -> bool {
+    a == b
+    //~^ ERROR binary operation `==` cannot be applied to type `std::mem::MaybeUninit<u8>`
+}
+
+fn grow(mut len: MaybeUninit<u32>) {
+    len += 1;
+    //~^ ERROR cannot be applied to type `std::mem::MaybeUninit<u32>`
+}
+
+fn main() {}
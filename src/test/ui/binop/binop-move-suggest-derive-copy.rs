@@ -0,0 +1,52 @@
+// Moving a value into an overloaded operator suggests deriving `Copy` on its
+// type when all of its fields are `Copy` already.
+
+use std::ops::{Add, Neg};
+
+struct Point {
+    x: i32,
+    y: i32,
+}
+
+impl Add for Point {
+    type Output = Point;
+
+    fn add(self, other: Point) -> Point {
+        Point { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+#[derive(Clone)]
+struct Angle(f64);
+
+impl Neg for Angle {
+    type Output = Angle;
+
+    fn neg(self) -> Angle {
+        Angle(-self.0)
+    }
+}
+
+struct Name(String);
+
+impl Add for Name {
+    type Output = Name;
+
+    fn add(self, other: Name) -> Name {
+        Name(self.0 + &other.0)
+    }
+}
+
+fn double(p: Point) -> Point {
+    p + p //~ ERROR use of moved value: `p`
+}
+
+fn flip(a: Angle) -> (Angle, Angle) {
+    (-a, a) //~ ERROR use of moved value: `a`
+}
+
+fn repeat(n: Name) -> Name {
+    n + n //~ ERROR use of moved value: `n`
+}
+
+fn main() {}
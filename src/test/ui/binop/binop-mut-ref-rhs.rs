@@ -0,0 +1,16 @@
+// run-rustfix
+// Operators that are only implemented for a shared reference RHS suggest borrowing immutably.
+
+fn main() {
+    let a = 1i32;
+    let mut b = 2i32;
+    let _ = a + &mut b;
+    //~^ ERROR cannot add `&mut i32` to `i32`
+    let mut c = 3u8;
+    c -= &mut 1u8;
+    //~^ ERROR binary assignment operation `-=` cannot be applied to type `u8`
+    let r = &mut b;
+    let _ = a * r;
+    //~^ ERROR cannot multiply `&mut i32` to `i32`
+    let _ = (c, r);
+}
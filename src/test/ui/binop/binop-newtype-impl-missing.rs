@@ -0,0 +1,22 @@
+// run-rustfix
+// Operators missing for a newtype whose field implements them suggest an implementation that
+// applies the operator to the field.
+
+#![allow(dead_code)]
+
+struct Meters(u32);
+
+struct Celsius {
+    degrees: f64,
+}
+
+fn total(a: Meters, b: Meters) -> Meters {
+    a + b //~ ERROR cannot add `Meters` to `Meters`
+}
+
+fn warm(mut t: Celsius, by: Celsius) -> Celsius {
+    t -= by; //~ ERROR binary assignment operation `-=` cannot be applied to type `Celsius`
+    t
+}
+
+fn main() {}
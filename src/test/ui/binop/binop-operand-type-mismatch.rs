@@ -0,0 +1,29 @@
+// Mismatched operands of builtin operators point at the left-hand side and the operator that
+// requires both to have the same type.
+
+fn add(a: u64, b: u32) {
+    let _ = a + b;
+    //~^ ERROR mismatched types
+    //~| ERROR cannot add `u32` to `u64`
+}
+
+fn compare(a: u64, b: u32) {
+    let _ = a == b;
+    //~^ ERROR mismatched types
+    //~| ERROR can't compare `u64` with `u32`
+}
+
+fn mul_assign(mut a: u64, b: u32) {
+    a *= b;
+    //~^ ERROR mismatched types
+    //~| ERROR cannot multiply-assign `u32` to `u64`
+}
+
+fn take_u32(_: u32) {}
+
+fn shift(a: u64, b: u32) {
+    take_u32(a << b);
+    //~^ ERROR mismatched types
+}
+
+fn main() {}
@@ -0,0 +1,24 @@
+// Arithmetic on an `Option` operand suggests applying the operator to the value inside it.
+
+fn next(opt: Option<i32>) -> Option<i32> {
+    opt + 1
+    //~^ ERROR cannot add `{integer}` to `std::option::Option<i32>`
+}
+
+fn total(opt: Option<i32>) -> i32 {
+    opt + 1
+    //~^ ERROR cannot add `{integer}` to `std::option::Option<i32>`
+}
+
+fn halve(len: Option<usize>) {
+    let half = len / 2;
+    //~^ ERROR cannot divide `std::option::Option<usize>` by `{integer}`
+    println!("{:?}", half);
+}
+
+fn shifted(v: i32, opt: Option<i32>) -> Option<i32> {
+    opt - v
+    //~^ ERROR cannot subtract `i32` from `std::option::Option<i32>`
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+// run-rustfix
+// Comparing an `Option<bool>` with a `bool` literal suggests wrapping the literal in `Some`.
+
+fn main() {
+    let opt = Some(false);
+    let _ = opt == true; //~ ERROR mismatched types
+    let _ = false != opt; //~ ERROR mismatched types
+}
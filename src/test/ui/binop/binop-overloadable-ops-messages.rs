@@ -0,0 +1,44 @@
+// Checks the E0369 message of every overloadable binary operator. The operands have different
+// types, so that the order in which they are mentioned is checked as well.
+//
+// `&&` and `||` are not overloadable: their operands are required to be `bool` instead.
+
+struct A;
+struct B;
+
+fn main() {
+    let a = A;
+    let b = B;
+
+    a + b; //~ ERROR cannot add `B` to `A`
+
+    a - b; //~ ERROR cannot subtract `B` from `A`
+
+    a * b; //~ ERROR cannot multiply `B` to `A`
+
+    a / b; //~ ERROR cannot divide `A` by `B`
+
+    a % b; //~ ERROR cannot mod `A` by `B`
+
+    a & b; //~ ERROR no implementation for `A & B`
+
+    a ^ b; //~ ERROR no implementation for `A ^ B`
+
+    a | b; //~ ERROR no implementation for `A | B`
+
+    a << b; //~ ERROR no implementation for `A << B`
+
+    a >> b; //~ ERROR no implementation for `A >> B`
+
+    a == b; //~ ERROR binary operation `==` cannot be applied to type `A`
+
+    a != b; //~ ERROR binary operation `!=` cannot be applied to type `A`
+
+    a < b; //~ ERROR binary operation `<` cannot be applied to type `A`
+
+    a <= b; //~ ERROR binary operation `<=` cannot be applied to type `A`
+
+    a > b; //~ ERROR binary operation `>` cannot be applied to type `A`
+
+    a >= b; //~ ERROR binary operation `>=` cannot be applied to type `A`
+}
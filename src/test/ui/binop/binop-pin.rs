@@ -0,0 +1,36 @@
+// Operators on `Pin` operands explain that `Pin` restricts access to the value it points to.
+
+use std::ops::AddAssign;
+use std::pin::Pin;
+
+fn next(count: Pin<&mut u32>) -> u32 {
+    count + 1
+    //~^ ERROR cannot add `{integer}` to `std::pin::Pin<&mut u32>`
+}
+
+fn bump(mut count: Pin<&mut u32>) {
+    count += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::pin::Pin<&mut u32>`
+}
+
+fn sum(a: Pin<&u64>, b: Pin<Box<u64>>) -> u64 {
+    a + b
+    //~^ ERROR cannot add `std::pin::Pin<std::boxed::Box<u64>>` to `std::pin::Pin<&u64>`
+}
+
+fn grow<T: AddAssign<T>>(mut value: Pin<&mut T>, step: T) {
+    value += step;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::pin::Pin<&mut T>`
+}
+
+fn scale<T>(mut value: Pin<&mut T>, factor: T) {
+    value *= factor;
+    //~^ ERROR binary assignment operation `*=` cannot be applied to type `std::pin::Pin<&mut T>`
+}
+
+fn half<T>(value: Pin<&T>, two: T) -> T {
+    value / two
+    //~^ ERROR cannot divide `std::pin::Pin<&T>` by `T`
+}
+
+fn main() {}
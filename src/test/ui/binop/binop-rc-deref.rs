@@ -0,0 +1,24 @@
+// Operators applied to `Rc`s and `Arc`s suggest applying them to the values they point to.
+
+use std::rc::Rc;
+use std::sync::Arc;
+
+fn main() {
+    let count = Rc::new(1u64);
+    let _ = count + 1;
+    //~^ ERROR cannot add `{integer}` to `std::rc::Rc<u64>`
+    let shared = Arc::new(2.5f32);
+    let _ = shared * shared.clone();
+    //~^ ERROR cannot multiply `std::sync::Arc<f32>` to `std::sync::Arc<f32>`
+    let mut sum = 0u64;
+    sum += count;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `u64`
+
+    // The contents of an `Rc` can neither be moved out of it nor modified.
+    let name = Rc::new(String::from("a"));
+    let _ = name + "b";
+    //~^ ERROR cannot add `&str` to `std::rc::Rc<std::string::String>`
+    let total = Rc::new(0u64);
+    total += 1;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::rc::Rc<u64>`
+}
@@ -0,0 +1,20 @@
+// check-pass
+// The expected type of a binary operation is used, through the `Output` of the operator trait, as
+// the expected type of its RHS. This lets the closure below know the type of its argument.
+
+use std::ops::Add;
+
+struct Pipe;
+
+impl<F> Add<F> for Pipe {
+    type Output = F;
+
+    fn add(self, f: F) -> F {
+        f
+    }
+}
+
+fn main() {
+    let f: fn(u32) -> u32 = Pipe + |x| x.count_ones();
+    assert_eq!(f(3), 2);
+}
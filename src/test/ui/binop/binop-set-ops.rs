@@ -0,0 +1,20 @@
+// run-rustfix
+// The set operators are only implemented for references to sets, so both operands are borrowed.
+
+use std::collections::{BTreeSet, HashSet};
+
+fn make() -> BTreeSet<char> {
+    BTreeSet::new()
+}
+
+fn main() {
+    let a: HashSet<u32> = HashSet::new();
+    let b: HashSet<u32> = HashSet::new();
+    let _ = a | b;
+    //~^ ERROR no implementation for
+    let c: BTreeSet<char> = BTreeSet::new();
+    let _ = c - make();
+    //~^ ERROR cannot subtract
+    let _ = make() ^ make();
+    //~^ ERROR no implementation for
+}
@@ -0,0 +1,25 @@
+// Functions and closures returning `bool` that are used as operands of `&&` or `||` suggest
+// calling them, like any other value that is expected to be a `bool`.
+
+fn is_ready() -> bool {
+    true
+}
+
+fn is_even(n: u32) -> bool {
+    n % 2 == 0
+}
+
+fn ready(other: bool) -> bool {
+    is_ready && other //~ ERROR mismatched types
+}
+
+fn even(other: bool) -> bool {
+    other || is_even //~ ERROR mismatched types
+}
+
+fn enabled(other: bool) -> bool {
+    let enabled = || other;
+    enabled && other //~ ERROR mismatched types
+}
+
+fn main() {}
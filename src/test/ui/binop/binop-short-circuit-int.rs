@@ -0,0 +1,23 @@
+// run-rustfix
+// Integers, floats and raw pointers used as operands of `&&` or `||` suggest comparing them with
+// zero, or testing them with `is_null()`.
+
+#![allow(dead_code)]
+
+fn integer(count: u32, flag: bool) -> bool {
+    count && flag //~ ERROR mismatched types
+}
+
+fn bitmask(bits: u8, mask: u8, flag: bool) -> bool {
+    flag || bits & mask //~ ERROR mismatched types
+}
+
+fn float(weight: f64, flag: bool) -> bool {
+    flag && weight //~ ERROR mismatched types
+}
+
+fn pointer(ptr: *const u8, flag: bool) -> bool {
+    ptr && flag //~ ERROR mismatched types
+}
+
+fn main() {}
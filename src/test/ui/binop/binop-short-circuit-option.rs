@@ -0,0 +1,21 @@
+// run-rustfix
+// Using an `Option` or a `Result` as an operand of `&&` or `||` suggests testing it with
+// `is_some()` or `is_ok()`.
+
+#![allow(dead_code)]
+
+struct User {
+    name: String,
+}
+
+fn greet(maybe_user: Option<User>, is_admin: bool) {
+    if maybe_user && is_admin { //~ ERROR mismatched types
+        println!("hello, {}", maybe_user.unwrap().name);
+    }
+}
+
+fn check(is_ready: bool, parsed: Result<u32, String>) -> bool {
+    is_ready || parsed //~ ERROR mismatched types
+}
+
+fn main() {}
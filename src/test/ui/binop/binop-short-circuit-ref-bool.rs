@@ -0,0 +1,22 @@
+// run-rustfix
+// Operands of `&&` and `||` that are `bool`s behind references suggest dereferencing them.
+
+#![allow(dead_code)]
+
+fn one_layer(enabled: &bool, ready: bool) -> bool {
+    enabled && ready //~ ERROR mismatched types
+}
+
+fn one_layer_rhs(ready: bool, enabled: &bool) -> bool {
+    ready || enabled //~ ERROR mismatched types
+}
+
+fn two_layers(flags: &[&bool], ready: bool) -> bool {
+    flags.iter().all(|enabled| enabled && ready) //~ ERROR mismatched types
+}
+
+fn two_layers_rhs(flags: &[&bool], ready: bool) -> bool {
+    flags.iter().any(|enabled| ready || enabled) //~ ERROR mismatched types
+}
+
+fn main() {}
@@ -0,0 +1,26 @@
+// Slices can't be added, suggest concatenating them instead.
+
+fn join(a: &[String], b: &[String]) -> Vec<String> {
+    a + b
+    //~^ ERROR cannot add `&[std::string::String]` to `&[std::string::String]`
+}
+
+fn join_vecs(x: Vec<char>, y: Vec<char>) -> Vec<char> {
+    &x[..] + &y[..]
+    //~^ ERROR cannot add `&[char]` to `&[char]`
+}
+
+fn join_arrays() {
+    let p = ['a', 'b'];
+    let q = ['c'];
+    let _ = &p + &q;
+    //~^ ERROR cannot add `&[char; 1]` to `&[char; 2]`
+}
+
+// No suggestion: the element types differ.
+fn join_mismatched(a: &[char], b: &[String]) {
+    let _ = a + b;
+    //~^ ERROR cannot add `&[std::string::String]` to `&[char]`
+}
+
+fn main() {}
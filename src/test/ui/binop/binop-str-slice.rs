@@ -0,0 +1,18 @@
+// Operators applied to the unsized `str` itself explain that `&str` is what is usually meant.
+
+fn exclaim(s: &str) -> String {
+    *s + "!"
+    //~^ ERROR cannot add `&str` to `str`
+}
+
+fn exclaim_tail(s: &str) -> String {
+    s[1..] + "!"
+    //~^ ERROR cannot add `&str` to `str`
+}
+
+fn exclaim_in_place(s: &mut str) {
+    *s += "!";
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `str`
+}
+
+fn main() {}
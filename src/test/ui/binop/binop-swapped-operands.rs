@@ -0,0 +1,31 @@
+// Operators implemented for their operands in the other order suggest swapping them, as long as
+// the operator is commutative and doesn't concatenate strings.
+
+use std::ops::{Mul, Sub};
+
+struct Meters(f64);
+struct Scale(f64);
+
+impl Mul<Meters> for Scale {
+    type Output = Meters;
+    fn mul(self, rhs: Meters) -> Meters {
+        Meters(self.0 * rhs.0)
+    }
+}
+
+impl Sub<Meters> for Scale {
+    type Output = Scale;
+    fn sub(self, rhs: Meters) -> Scale {
+        Scale(self.0 - rhs.0)
+    }
+}
+
+fn main() {
+    let _ = Meters(1.0) * Scale(2.0);
+    //~^ ERROR cannot multiply `Scale` to `Meters`
+    let _ = Meters(1.0) - Scale(2.0);
+    //~^ ERROR cannot subtract `Scale` from `Meters`
+    let s = String::from("b");
+    let _ = "a" + s;
+    //~^ ERROR cannot add `std::string::String` to `&str`
+}
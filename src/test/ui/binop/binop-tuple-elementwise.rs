@@ -0,0 +1,31 @@
+// Operators that aren't implemented for tuples get a note showing how to apply them to each
+// element, when that works for all the elements.
+
+fn main() {
+    let a = (1, 2.0);
+    let b = (3, 4.0);
+    let _ = a + b;
+    //~^ ERROR cannot add `({integer}, {float})` to `({integer}, {float})`
+    let _ = a * (2, 0.5 + 1.0);
+    //~^ ERROR cannot multiply `({integer}, {float})` to `({integer}, {float})`
+
+    let mut c = (1u8,);
+    c <<= (2u32,);
+    //~^ ERROR binary assignment operation `<<=` cannot be applied to type `(u8,)`
+
+    let mut d = (1, 2);
+    d ^= (3, 4);
+    //~^ ERROR binary assignment operation `^=` cannot be applied to type `({integer}, {integer})`
+
+    // No note: the elements are not all builtin types the operator applies to, or the operands
+    // would be evaluated once per element.
+    let s = (String::new(), 1);
+    let _ = s - s;
+    //~^ ERROR cannot subtract `(std::string::String, {integer})` from
+    let _ = make() + make();
+    //~^ ERROR cannot add `(i32, i32)` to `(i32, i32)`
+}
+
+fn make() -> (i32, i32) {
+    (0, 0)
+}
@@ -0,0 +1,13 @@
+// Type aliases are expanded before operator lookup, so operator errors name the underlying
+// types of aliased operands rather than the aliases.
+
+struct Length(f64);
+
+type Meters = Length;
+type Factor = f64;
+
+fn main() {
+    let a: Meters = Length(1.0);
+    let b: Factor = 2.0;
+    let _ = a * b; //~ ERROR cannot multiply `f64` to `Length`
+}
@@ -0,0 +1,31 @@
+// Operator errors on a type parameter note when the parameter's default supports the operator.
+
+struct Pair<T = i32> {
+    a: T,
+    b: T,
+}
+
+impl<T> Pair<T> {
+    fn sum(self) -> T {
+        self.a + self.b
+        //~^ ERROR cannot add `T` to `T`
+    }
+
+    fn add_to_first(&mut self, x: T) {
+        self.a += x;
+        //~^ ERROR binary assignment operation `+=` cannot be applied to type `T`
+    }
+}
+
+struct Named<T = String> {
+    name: T,
+}
+
+impl<T> Named<T> {
+    fn diff(self, other: T) -> T {
+        self.name - other
+        //~^ ERROR cannot subtract `T` from `T`
+    }
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// `Vec`s can't be added, suggest appending one to the other instead.
+
+fn main() {
+    let mut a: Vec<i32> = vec![1, 2];
+    let b: Vec<i32> = vec![3];
+    let _c = a + b;
+    //~^ ERROR cannot add `std::vec::Vec<i32>` to `std::vec::Vec<i32>`
+    a += b;
+    //~^ ERROR binary assignment operation `+=` cannot be applied to type `std::vec::Vec<i32>`
+    a + b;
+    //~^ ERROR cannot add `std::vec::Vec<i32>` to `std::vec::Vec<i32>`
+
+    // No suggestion: the element types differ.
+    let d: Vec<u8> = vec![4];
+    let _e = a + d;
+    //~^ ERROR cannot add `std::vec::Vec<u8>` to `std::vec::Vec<i32>`
+}
@@ -0,0 +1,30 @@
+// run-pass
+// Test that `|`, `^` and `&` bind more tightly than comparisons, so that a comparison can only be
+// an operand of one of them if it is parenthesized.
+
+fn main() {
+    let (t, f) = (true, false);
+
+    // `t | f == f` would be `true` if it parsed as `t | (f == f)`.
+    assert_eq!(t | f == f, (t | f) == f);
+    assert!(!(t | f == f));
+    assert!(t | (f == f));
+
+    assert_eq!(f & f == f, (f & f) == f);
+    assert!(f & f == f);
+    assert!(!(f & (f == f)));
+
+    assert_eq!(t ^ t == t, (t ^ t) == t);
+    assert!(!(t ^ t == t));
+    assert!(t ^ (t == t));
+
+    assert_eq!(f == t | t, f == (t | t));
+    assert!(!(f == t | t));
+
+    // With integer operands, the other grouping would not even type-check.
+    let flags = 0b0101_u8;
+    let mask = 0b0011_u8;
+    assert!(flags | mask == 0b0111);
+    assert!(flags & mask != 0);
+    assert!(flags ^ mask == 0b0110);
+}
@@ -0,0 +1,20 @@
+// Operators used on several type parameters missing the same kind of bound get one suggestion
+// restricting all of them, and so do several operators used on the same type parameter.
+
+fn foo<A, B, C>(a: A, b: B, c: C) {
+    let _ = a + a;
+    //~^ ERROR cannot add `A` to `A`
+    let _ = b + b;
+    //~^ ERROR cannot add `B` to `B`
+    let _ = c - c;
+    //~^ ERROR cannot subtract `C` from `C`
+}
+
+fn bar<T>(x: T, y: T) {
+    let _ = x + y;
+    //~^ ERROR cannot add `T` to `T`
+    let _ = x * y;
+    //~^ ERROR cannot multiply `T` to `T`
+}
+
+fn main() {}
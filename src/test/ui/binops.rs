@@ -1,6 +1,6 @@
 // run-pass
 
-#![allow(non_camel_case_types)]
+#![allow(non_camel_case_types, self_comparisons)]
 // Binop corner cases
 
 fn test_nil() {
@@ -0,0 +1,15 @@
+// run-rustfix
+// Assigning to an entry of a map through indexing suggests `insert`.
+
+use std::collections::{BTreeMap, HashMap};
+
+fn main() {
+    let mut scores = HashMap::new();
+    scores.insert("bob", 3);
+    scores["alice"] = 10; //~ ERROR cannot assign to data in an index of
+
+    let mut names = BTreeMap::new();
+    names.insert(String::from("id"), String::new());
+    let key = String::from("id");
+    names[&key] = String::from("ferris"); //~ ERROR cannot assign to data in an index of
+}
@@ -0,0 +1,25 @@
+// Check that a borrow conflict in `self.field op= rhs` explains that the
+// value of the right-hand side keeps all of `self` borrowed.
+
+struct Log {
+    text: String,
+    name: String,
+}
+
+impl Log {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn append_name(&mut self) {
+        self.text += self.name();
+        //~^ ERROR cannot borrow `self.text` as mutable because it is also borrowed as immutable
+    }
+
+    fn append_name_field(&mut self) {
+        // Borrowing only the field that is needed is accepted.
+        self.text += &self.name;
+    }
+}
+
+fn main() {}
@@ -0,0 +1,27 @@
+// Modifying the indexed content of a type that only implements `Index` explains that `IndexMut`
+// is missing, while indexing through a shared reference to a type that implements `IndexMut`
+// points at the reference instead.
+
+use std::ops::Index;
+
+struct Grid<T> {
+    cells: Vec<T>,
+}
+
+impl<T> Index<usize> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.cells[index]
+    }
+}
+
+fn set(grid: &mut Grid<u8>) {
+    grid[0] = 1; //~ ERROR cannot assign to data in an index of `Grid<u8>`
+}
+
+fn clear(v: &Vec<u8>) {
+    v[0] = 0; //~ ERROR cannot borrow `*v` as mutable, as it is behind a `&` reference
+}
+
+fn main() {}
@@ -0,0 +1,12 @@
+// Comparing two closures notes that closures can't be compared.
+
+fn main() {
+    let f = |x: i32| x + 1;
+    let g = |x: i32| x + 1;
+    let _ = f == g;
+    //~^ ERROR binary operation `==` cannot be applied to type
+    //~| NOTE closures in Rust do not implement `PartialEq`
+    let _ = f != f;
+    //~^ ERROR binary operation `!=` cannot be applied to type
+    //~| NOTE closures in Rust do not implement `PartialEq`
+}
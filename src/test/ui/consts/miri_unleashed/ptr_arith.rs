@@ -1,6 +1,6 @@
 // compile-flags: -Zunleash-the-miri-inside-of-you
 #![feature(core_intrinsics)]
-#![allow(const_err)]
+#![allow(const_err, self_comparisons)]
 
 // During CTFE, we prevent pointer comparison and pointer-to-int casts.
 
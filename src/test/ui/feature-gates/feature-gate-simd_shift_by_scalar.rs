@@ -0,0 +1,13 @@
+// Shifting an integer vector by a scalar amount is gated.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct u32x4(u32, u32, u32, u32);
+
+fn main() {
+    let v = u32x4(1, 2, 3, 4);
+    let _ = v << 3u32; //~ ERROR no implementation for `u32x4 << u32`
+}
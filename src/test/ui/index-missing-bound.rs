@@ -0,0 +1,14 @@
+// Indexing a value of a type parameter suggests the `Index` or `IndexMut` bound it is missing,
+// like the other operators do.
+
+fn get<T>(v: T) {
+    let _ = &v[0];
+    //~^ ERROR cannot index into a value of type `T`
+}
+
+fn set<C, K>(mut c: C, k: K) {
+    c[k] = 1;
+    //~^ ERROR cannot index into a value of type `C`
+}
+
+fn main() {}
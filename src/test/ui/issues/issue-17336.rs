@@ -1,5 +1,5 @@
 // build-pass
-#![allow(unused_must_use)]
+#![allow(unused_must_use, self_comparisons)]
 #[allow(dead_code)]
 fn check(a: &str) {
     let x = a as *const str;
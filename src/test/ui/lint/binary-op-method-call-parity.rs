@@ -0,0 +1,32 @@
+// run-rustfix
+
+#![deny(binary_op_method_call_parity)]
+
+use std::ops::{Add, Mul, Shl, Sub};
+
+#[derive(Clone, Copy)]
+struct Meters(f64);
+
+impl Add for Meters {
+    type Output = Meters;
+
+    fn add(self, other: Meters) -> Meters {
+        Meters(self.0 + other.0)
+    }
+}
+
+fn main() {
+    let (a, b, c): (i32, i32, i32) = (1, 2, 3);
+    let _ = a.add(b); //~ ERROR can be written with the `+` operator
+    let _ = a.add(b).pow(2); //~ ERROR can be written with the `+` operator
+    let _ = a * b.sub(c); //~ ERROR can be written with the `-` operator
+    let _ = a - b.sub(c); //~ ERROR can be written with the `-` operator
+    let _ = b.sub(c) - a; //~ ERROR can be written with the `-` operator
+    let _ = a.mul(b + c); //~ ERROR can be written with the `*` operator
+    let _ = (a as u8).shl(b); //~ ERROR can be written with the `<<` operator
+    let _ = Meters(1.0).add(Meters(2.0)); //~ ERROR can be written with the `+` operator
+
+    // The receiver is dereferenced to find the method, which `+` doesn't do.
+    let m = &Meters(1.0);
+    let _ = m.add(Meters(2.0));
+}
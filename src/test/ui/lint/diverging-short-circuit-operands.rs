@@ -0,0 +1,22 @@
+#![deny(diverging_short_circuit_operands)]
+#![allow(unreachable_code)]
+
+fn check(a: bool, b: bool) -> Result<bool, ()> {
+    let x = a && return Err(());
+    //~^ ERROR the right-hand side of `&&` diverges, so this always evaluates to `false`
+    let y = a == b || unreachable!();
+    //~^ ERROR the right-hand side of `||` diverges, so this always evaluates to `true`
+    Ok(x && y)
+}
+
+fn statements(a: bool, values: &[u32]) {
+    a || panic!("a must be set");
+    for &v in values {
+        v > 2 && { continue };
+    }
+}
+
+fn main() {
+    let _ = check(true, false);
+    statements(true, &[]);
+}
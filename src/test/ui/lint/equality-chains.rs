@@ -0,0 +1,40 @@
+// run-rustfix
+
+#![deny(equality_chains)]
+
+#[derive(PartialEq, Eq)]
+enum Kind {
+    A,
+    B,
+    C,
+    D,
+}
+
+const ZERO: i32 = 0;
+
+fn next(n: &mut i32) -> i32 {
+    *n += 1;
+    *n
+}
+
+fn main() {
+    let c = 'x';
+    let (mut n, a, b) = (5, 1, 2);
+    let kind = Kind::D;
+    let s = String::new();
+
+    let _ = c == 'a' || c == 'e' || c == 'i' || c == 'o' || c == 'u'; //~ ERROR compared with 5
+    let _ = 'a' == c || 'b' == c || c == 'c'; //~ ERROR compared with 3
+    let _ = n == ZERO || n == -1 || n == 1; //~ ERROR compared with 3
+    let _ = kind == Kind::A || kind == Kind::B || kind == Kind::C; //~ ERROR compared with 3
+    let _ = n == a || n == b || n == a + b; //~ ERROR compared with 3
+    if n == 1 || (n == 2 || n == 3) {} //~ ERROR compared with 3
+
+    // Too short, mixed with other conditions, or not comparing the same place.
+    let _ = c == 'a' || c == 'b';
+    let _ = c == 'a' || n == 1 || c == 'b';
+    let _ = c == 'a' || c != 'b' || c == 'c';
+    let _ = next(&mut n) == 1 || next(&mut n) == 2 || next(&mut n) == 3;
+    // `String == &str` has no `matches!` or `contains` equivalent.
+    let _ = s == "a" || s == "b" || s == "c";
+}
@@ -0,0 +1,23 @@
+// check-pass
+
+fn a() {}
+fn b() {}
+
+fn main() {
+    let f: fn() = a;
+    let g: fn() = b;
+
+    let _ = f == g;
+    //~^ WARN function pointer comparisons do not produce meaningful results
+    let _ = f != a as fn();
+    //~^ WARN function pointer comparisons do not produce meaningful results
+    let _ = &f == &g;
+    //~^ WARN function pointer comparisons do not produce meaningful results
+
+    #[allow(unpredictable_function_pointer_comparisons)]
+    let _ = f == g;
+
+    // `Option<fn()>` is not a function pointer, comparing it to `None` is fine.
+    let h: Option<fn()> = Some(a);
+    let _ = h == None;
+}
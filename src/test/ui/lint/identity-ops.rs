@@ -0,0 +1,43 @@
+// run-rustfix
+
+#![deny(identity_ops)]
+#![allow(dead_code)]
+
+const FLAG: u32 = 0;
+const MASK: u32 = 0b1010;
+
+trait Zero {
+    const ZERO: u32;
+}
+
+macro_rules! or_zero {
+    ($x:expr) => {
+        $x | 0
+    };
+}
+
+fn literals(x: u32, y: i64) {
+    let _ = x | 0; //~ ERROR this `|` operation has no effect
+    let _ = x << 0; //~ ERROR this `<<` operation has no effect
+    let _ = y * 1; //~ ERROR this `*` operation has no effect
+    let _ = 0 + y; //~ ERROR this `+` operation has no effect
+    let _ = x.count_ones() - 0; //~ ERROR this `-` operation has no effect
+    let _ = y / 1; //~ ERROR this `/` operation has no effect
+}
+
+fn named_constants(x: u32) {
+    let _ = x | FLAG; //~ ERROR this `|` operation has no effect, as `FLAG` is `0`
+    let _ = x | MASK;
+}
+
+fn not_linted<T: Zero>(x: u32, r: &u32) {
+    let _ = 0 - x;
+    let _ = 1 / x;
+    let _ = x & 0;
+    let _ = x | T::ZERO;
+    let _ = r + 0;
+    let _ = 1.0 * 2.0;
+    let _ = or_zero!(x);
+}
+
+fn main() {}
@@ -0,0 +1,17 @@
+// Shifts by a literal amount are checked during type-checking, after integer fallback, and
+// compound assignments and closures are checked as well.
+
+#![deny(arithmetic_overflow)]
+
+fn main() {
+    let x = 1;
+    let _ = x << 32; //~ ERROR this arithmetic operation will overflow: `i32` is only 32 bits wide
+
+    let mut y = 1u16;
+    y >>= 16; //~ ERROR this arithmetic operation will overflow: `u16` is only 16 bits wide
+
+    let _ = |z: u8| z << 8; //~ ERROR this arithmetic operation will overflow: `u8` is only 8 bits
+
+    let _ = 1u64 << 63;
+    let _ = 1u32 << -0;
+}
@@ -61,6 +61,7 @@ pub fn main() {
       let n = n << 8; //~ WARN: arithmetic operation will overflow
 
       let n = 1u8 << -8; //~ WARN: arithmetic operation will overflow
+      //~^ WARN: shifting by a negative amount
 
       let n = 1i8<<(1isize+-1);
 
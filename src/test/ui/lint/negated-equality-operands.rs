@@ -0,0 +1,24 @@
+// run-rustfix
+
+#![deny(negated_equality_operands)]
+#![allow(unused_parens)]
+
+struct Flags {
+    ready: bool,
+}
+
+fn check(flag: bool, other: bool, flags: Flags, n: u8) {
+    let _ = !flag == other; //~ ERROR `!` applies to `flag` only
+    let _ = !flags.ready != flag; //~ ERROR `!` applies to `flags.ready` only
+
+    let _ = (!flag) == other;
+    let _ = !(flag) == other;
+    let _ = !(flag == other);
+    let _ = other == !flag;
+    let _ = !n == 0;
+    let _ = !other.clone() == flag;
+}
+
+fn main() {
+    check(true, false, Flags { ready: true }, 0);
+}
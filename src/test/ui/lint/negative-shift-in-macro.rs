@@ -0,0 +1,13 @@
+// Shifts by a negative amount written in a macro call are still reported as overflowing, even
+// though `negative_shift` doesn't lint on code from macros.
+
+macro_rules! shl {
+    ($x:expr, $n:expr) => {
+        $x << $n
+    };
+}
+
+fn main() {
+    let _ = shl!(1u32, -1);
+    //~^ ERROR this arithmetic operation will overflow
+}
@@ -0,0 +1,16 @@
+// run-rustfix
+
+#![deny(negative_shift)]
+#![allow(arithmetic_overflow)]
+
+fn shift(x: u32, mut y: i64) -> i64 {
+    let _ = x << -1; //~ ERROR shifting by a negative amount
+    y >>= -2; //~ ERROR shifting by a negative amount
+
+    let _ = x << -0;
+    y
+}
+
+fn main() {
+    shift(1, 16);
+}
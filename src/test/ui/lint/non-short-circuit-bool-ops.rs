@@ -0,0 +1,40 @@
+// run-rustfix
+// `&` and `|` on booleans evaluate their right-hand side regardless of the left-hand side.
+
+#![deny(non_short_circuit_bool_ops)]
+
+fn check_a() -> bool {
+    true
+}
+
+fn check_b() -> bool {
+    false
+}
+
+struct Flags {
+    ready: bool,
+}
+
+impl Flags {
+    fn is_ready(&self) -> bool {
+        self.ready
+    }
+}
+
+fn main() {
+    let a = check_a();
+    let flags = Flags { ready: true };
+
+    if check_a() & check_b() {} //~ ERROR `&` evaluates its right-hand side even when
+    let _ = a | flags.is_ready(); //~ ERROR `|` evaluates its right-hand side even when
+    let _ = a & !check_b(); //~ ERROR `&` evaluates its right-hand side even when
+
+    // Nothing is called on the right-hand side.
+    let _ = check_a() & a;
+    let _ = a | flags.ready;
+
+    // Compound assignment has no short-circuiting counterpart.
+    let mut any = false;
+    any |= check_b();
+    let _ = any;
+}
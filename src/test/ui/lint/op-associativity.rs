@@ -0,0 +1,19 @@
+// run-rustfix
+
+#![deny(op_associativity)]
+
+fn main() {
+    let (a, b, c, d) = (10, 3, 2, 1);
+    let _ = a - b - c; //~ ERROR `-` is evaluated from left to right
+    let _ = a / b / c; //~ ERROR `/` is evaluated from left to right
+    let _ = a - b - c - d; //~ ERROR `-` is evaluated from left to right
+    let _ = (a - b - c) - d; //~ ERROR `-` is evaluated from left to right
+    let _ = 2.0 * (a as f64 / 4.0 / 0.5); //~ ERROR `/` is evaluated from left to right
+
+    // The order is already explicit, or the operators differ.
+    let _ = (a - b) - c;
+    let _ = a - (b - c);
+    let _ = a - b + c;
+    let _ = a / b - c;
+    let _ = a / b % c;
+}
@@ -0,0 +1,26 @@
+#![deny(op_on_zst)]
+
+use std::ops::{Add, Mul};
+
+#[derive(Clone, Copy)]
+struct Unit;
+
+impl Add for Unit {
+    type Output = Unit;
+    fn add(self, _: Unit) -> Unit {
+        Unit
+    }
+}
+
+impl Mul<u32> for Unit {
+    type Output = u32;
+    fn mul(self, rhs: u32) -> u32 {
+        rhs
+    }
+}
+
+fn main() {
+    let _ = Unit + Unit; //~ ERROR binary operation `+` applied to a zero-sized type
+    let _ = Unit * 3; //~ ERROR binary operation `*` applied to a zero-sized type
+    let _ = 1u32 + 2u32;
+}
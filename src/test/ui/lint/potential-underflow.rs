@@ -0,0 +1,22 @@
+// run-rustfix
+
+#![deny(potential_underflow)]
+
+struct Counter {
+    count: u32,
+}
+
+fn count_down(mut n: usize, counter: &mut Counter, slot: &mut u8, mut signed: i32) {
+    n -= 1; //~ ERROR this subtraction underflows if `n` is `0`
+    counter.count -= 1; //~ ERROR this subtraction underflows
+    *slot -= 1; //~ ERROR this subtraction underflows if `*slot` is `0`
+
+    n -= 2;
+    signed -= 1;
+    n = n - 1;
+    let _ = (n, signed);
+}
+
+fn main() {
+    count_down(1, &mut Counter { count: 1 }, &mut 1, 1);
+}
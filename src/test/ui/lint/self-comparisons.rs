@@ -0,0 +1,39 @@
+// check-pass
+// Comparing a place with itself is constant, except for floats, where it checks for NaN.
+
+struct Point {
+    x: i32,
+    y: f64,
+}
+
+static LIMIT: u8 = 3;
+
+macro_rules! same {
+    ($e:expr) => {
+        $e == $e
+    };
+}
+
+fn main() {
+    let a = 1;
+    let p = Point { x: 1, y: 2.0 };
+    let q = &a;
+    let f = 0.5_f32;
+    let g = &f;
+
+    let _ = a == a; //~ WARN comparison of a value with itself is always `true`
+    let _ = a < a; //~ WARN comparison of a value with itself is always `false`
+    let _ = p.x != p.x; //~ WARN comparison of a value with itself is always `false`
+    let _ = *q <= *q; //~ WARN comparison of a value with itself is always `true`
+    let _ = LIMIT == LIMIT; //~ WARN comparison of a value with itself is always `true`
+
+    let _ = f != f; //~ WARN comparison of a float with itself only checks for NaN
+    let _ = p.y == p.y; //~ WARN comparison of a float with itself only checks for NaN
+    let _ = *g == *g; //~ WARN comparison of a float with itself only checks for NaN
+    let _ = f > f; //~ WARN comparison of a value with itself is always `false`
+
+    // Not the same place, or not written out in the comparison itself.
+    let _ = a == *q;
+    let _ = p.x == a;
+    let _ = same!(a);
+}
@@ -0,0 +1,66 @@
+// check-pass
+// Negating a newtype whose field cannot be negated needs its meaning spelled out.
+
+#![warn(undocumented_newtype_neg)]
+
+use std::ops::Neg;
+
+struct Inner;
+
+struct Wrapper(Inner);
+
+impl Neg for Wrapper { //~ WARN `Wrapper` implements `Neg`, but the type it wraps does not
+    type Output = Wrapper;
+    fn neg(self) -> Wrapper {
+        self
+    }
+}
+
+struct Generic<T>(T);
+
+impl<T> Neg for Generic<T> { //~ WARN `Generic<T>` implements `Neg`, but the type it wraps does not
+    type Output = Generic<T>;
+    fn neg(self) -> Generic<T> {
+        self
+    }
+}
+
+struct Unsigned(u32);
+
+/// Negation wraps around, like `u32::wrapping_neg`.
+impl Neg for Unsigned {
+    type Output = Unsigned;
+    fn neg(self) -> Unsigned {
+        Unsigned(self.0.wrapping_neg())
+    }
+}
+
+struct Counter(u64);
+
+impl Neg for Counter {
+    type Output = Counter;
+    /// Counters saturate at zero, so negating one always yields zero.
+    fn neg(self) -> Counter {
+        Counter(0)
+    }
+}
+
+struct Signed(i32);
+
+impl Neg for Signed {
+    type Output = Signed;
+    fn neg(self) -> Signed {
+        Signed(-self.0)
+    }
+}
+
+struct Bounded<T>(T);
+
+impl<T: Neg<Output = T>> Neg for Bounded<T> {
+    type Output = Bounded<T>;
+    fn neg(self) -> Bounded<T> {
+        Bounded(-self.0)
+    }
+}
+
+fn main() {}
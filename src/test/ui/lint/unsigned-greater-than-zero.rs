@@ -0,0 +1,16 @@
+// run-rustfix
+
+#![deny(unsigned_greater_than_zero)]
+
+fn check(len: usize, count: u8, delta: i32) -> bool {
+    let _ = len > 0; //~ ERROR `usize` is unsigned
+    let _ = 0 < count; //~ ERROR `u8` is unsigned
+    let _ = len > 1;
+    let _ = delta > 0;
+    let _ = len != 0;
+    count as u32 > 0 //~ ERROR `u32` is unsigned
+}
+
+fn main() {
+    check(1, 2, 3);
+}
@@ -0,0 +1,44 @@
+// check-pass
+
+use std::ops::BitXor;
+
+struct Flags(u8);
+
+impl BitXor<u8> for Flags {
+    type Output = Flags;
+
+    fn bitxor(self, rhs: u8) -> Flags {
+        Flags(self.0 ^ rhs)
+    }
+}
+
+macro_rules! xor {
+    ($a:expr, $b:expr) => {
+        $a ^ $b
+    };
+}
+
+fn main() {
+    let side: u32 = 3;
+    let _ = side ^ 2;
+    //~^ WARN `^` is bitwise XOR, not exponentiation
+    let _ = side + 1 ^ 3;
+    //~^ WARN `^` is bitwise XOR, not exponentiation
+    let _ = (side + 1) ^ 10;
+    //~^ WARN `^` is bitwise XOR, not exponentiation
+
+    // Not exponentiation to a small power.
+    let _ = side ^ 1;
+    let _ = side ^ 11;
+
+    // Looks like bit twiddling.
+    let _ = 4 ^ 2;
+    let _ = (side & 0xf) ^ 2;
+    let _ = (side << 1) ^ 2;
+
+    // Overloaded `^`.
+    let _ = Flags(1) ^ 2;
+
+    // Macro expansions.
+    let _ = xor!(side, 2);
+}
@@ -0,0 +1,33 @@
+// run-rustfix
+// The missing `Output` of an operator trait is suggested from the method's return type.
+
+use std::ops::{Add, Neg, Sub};
+
+#[derive(Debug)]
+struct Meters(u32);
+
+impl Add<u32> for Meters {
+    //~^ ERROR not all trait items implemented, missing: `Output`
+    fn add(self, rhs: u32) -> Meters {
+        Meters(self.0 + rhs)
+    }
+}
+
+impl Neg for Meters {
+    //~^ ERROR not all trait items implemented, missing: `Output`
+    fn neg(self) -> Self {
+        self
+    }
+}
+
+impl Sub for Meters {
+    type Output = Meters;
+
+    fn sub(self, rhs: Meters) -> Meters {
+        Meters(self.0 - rhs.0)
+    }
+}
+
+fn main() {
+    println!("{:?}", -(Meters(1) + 2) - Meters(1));
+}
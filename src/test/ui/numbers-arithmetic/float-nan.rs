@@ -1,4 +1,7 @@
 // run-pass
+
+#![allow(self_comparisons)]
+
 use std::f64;
 
 pub fn main() {
@@ -0,0 +1,25 @@
+// run-rustfix
+// `T: Add == U` in a `where` clause is recovered as a constraint on the `Output` of `Add`.
+
+use std::ops::{Add, Neg};
+
+fn sum<T, U>(a: T, b: T) -> U
+where
+    T: Add == U,
+    //~^ ERROR equality constraints on operator traits must use the `Output` associated type
+{
+    a + b
+}
+
+fn negate<T>(a: T) -> T
+where
+    T: Neg = T,
+    //~^ ERROR equality constraints on operator traits must use the `Output` associated type
+{
+    -a
+}
+
+fn main() {
+    let _: i32 = sum(1, 2);
+    let _ = negate(1);
+}
@@ -0,0 +1,22 @@
+#![crate_type = "lib"]
+
+#[non_exhaustive]
+pub struct NonExhaustiveStruct;
+
+#[non_exhaustive]
+pub enum NonExhaustiveEnum {
+    A,
+}
+
+pub enum NonExhaustiveVariant {
+    #[non_exhaustive]
+    A,
+}
+
+pub struct External<T>(pub T);
+
+pub struct Private(());
+
+pub struct PrivateNamed {
+    _unit: (),
+}
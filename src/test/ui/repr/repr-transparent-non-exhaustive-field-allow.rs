@@ -0,0 +1,27 @@
+// aux-build: repr-transparent-non-exhaustive.rs
+
+// The lint level can be set on individual fields.
+
+#![deny(repr_transparent_external_non_exhaustive)]
+
+extern crate repr_transparent_non_exhaustive;
+
+use repr_transparent_non_exhaustive::{NonExhaustiveEnum, NonExhaustiveStruct};
+
+#[repr(transparent)]
+pub struct T1(
+    u32,
+    #[allow(repr_transparent_external_non_exhaustive)] NonExhaustiveStruct,
+    NonExhaustiveEnum,
+    //~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+    //~| WARN this was previously accepted by the compiler
+);
+
+#[repr(transparent)]
+pub struct T2 {
+    x: u32,
+    #[allow(repr_transparent_external_non_exhaustive)]
+    y: NonExhaustiveStruct,
+}
+
+fn main() {}
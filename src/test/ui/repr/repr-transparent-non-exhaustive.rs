@@ -0,0 +1,91 @@
+// aux-build: repr-transparent-non-exhaustive.rs
+
+#![deny(repr_transparent_external_non_exhaustive)]
+
+extern crate repr_transparent_non_exhaustive;
+
+use repr_transparent_non_exhaustive::{
+    External, NonExhaustiveEnum, NonExhaustiveStruct, NonExhaustiveVariant,
+};
+use std::marker::{PhantomData, PhantomPinned};
+
+#[non_exhaustive]
+pub struct LocalNonExhaustive;
+
+pub struct Local(NonExhaustiveStruct);
+
+#[repr(transparent)]
+pub struct T1(u32, LocalNonExhaustive);
+
+#[repr(transparent)]
+pub struct T2(u32, NonExhaustiveStruct);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T3(u32, NonExhaustiveEnum);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T4(u32, NonExhaustiveVariant);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T5(u32, (((), NonExhaustiveStruct), ()));
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T6(u32, External<NonExhaustiveStruct>);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T7(u32, Local);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+// Types that are zero-sized forever are fine, whatever they contain.
+#[repr(transparent)]
+pub struct T8(u32, PhantomPinned);
+
+#[repr(transparent)]
+pub struct T9(u32, PhantomData<NonExhaustiveStruct>);
+
+#[repr(transparent)]
+pub struct T10(u32, [Local; 0]);
+
+#[repr(transparent)]
+pub struct T11(u32, [NonExhaustiveStruct; 0]);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+// These are only problematic when instantiated with their defaults.
+#[repr(transparent)]
+pub struct T12<M = NonExhaustiveStruct>(M);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T13<A = NonExhaustiveStruct, B = External<A>>(B);
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive type
+//~| WARN this was previously accepted by the compiler
+
+// Parameters without defaults are checked where the type is instantiated.
+#[repr(transparent)]
+pub struct T14<M>(M);
+
+// All the external non-exhaustive types of an item are reported together.
+#[repr(transparent)]
+pub struct T15(u32, ((NonExhaustiveVariant, ()), NonExhaustiveEnum, [NonExhaustiveStruct; 0]));
+//~^ ERROR zero-sized field in transparent struct can't contain external non exhaustive types
+//~| WARN this was previously accepted by the compiler
+
+#[repr(transparent)]
+pub struct T16(NonExhaustiveStruct, u32, NonExhaustiveEnum);
+//~^ ERROR zero-sized fields in transparent struct can't contain external non exhaustive types
+//~| WARN this was previously accepted by the compiler
+
+fn main() {}
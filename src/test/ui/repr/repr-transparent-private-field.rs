@@ -0,0 +1,31 @@
+// check-pass
+// aux-build: repr-transparent-non-exhaustive.rs
+
+// The lint only looks for `#[non_exhaustive]` types. Zero-sized types from other crates that
+// merely have private fields are not `#[non_exhaustive]`, so they don't trigger it.
+
+#![deny(repr_transparent_external_non_exhaustive)]
+
+extern crate repr_transparent_non_exhaustive;
+
+use repr_transparent_non_exhaustive::{External, Private, PrivateNamed};
+
+#[repr(transparent)]
+pub struct T1(u32, Private);
+
+#[repr(transparent)]
+pub struct T2(u32, PrivateNamed);
+
+#[repr(transparent)]
+pub struct T3(u32, External<Private>);
+
+#[repr(transparent)]
+pub struct T4(u32, [PrivateNamed; 0]);
+
+#[repr(transparent)]
+pub struct T5 {
+    pub value: u32,
+    pub marker: (Private, PrivateNamed),
+}
+
+fn main() {}
@@ -0,0 +1,48 @@
+// run-pass
+
+// Transparent types have the same size as their non-zero-sized field, including when the field
+// has non-standard alignment or is itself a union or an enum.
+
+#![allow(dead_code)]
+
+use std::marker::PhantomData;
+use std::mem::size_of;
+
+#[repr(align(16))]
+struct Aligned(u8);
+
+#[repr(packed)]
+struct Packed(u8, u32);
+
+union Union {
+    a: u16,
+    b: [u8; 3],
+}
+
+enum Enum {
+    A(u32),
+    B,
+}
+
+#[repr(transparent)]
+struct WrapAligned(Aligned, ());
+
+#[repr(transparent)]
+struct WrapPacked(PhantomData<u64>, Packed);
+
+#[repr(transparent)]
+struct WrapUnion(Union, [u8; 0]);
+
+#[repr(transparent)]
+struct WrapEnum(Enum);
+
+#[repr(transparent)]
+struct WrapWrap(WrapAligned, PhantomData<WrapPacked>);
+
+fn main() {
+    assert_eq!(size_of::<WrapAligned>(), size_of::<Aligned>());
+    assert_eq!(size_of::<WrapPacked>(), size_of::<Packed>());
+    assert_eq!(size_of::<WrapUnion>(), size_of::<Union>());
+    assert_eq!(size_of::<WrapEnum>(), size_of::<Enum>());
+    assert_eq!(size_of::<WrapWrap>(), size_of::<Aligned>());
+}
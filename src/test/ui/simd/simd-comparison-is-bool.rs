@@ -0,0 +1,20 @@
+// run-pass
+// Comparison operators on SIMD vectors are never builtin, they use the vector's `PartialEq` and
+// `PartialOrd` impls, so they produce a `bool` that can be used as a condition.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, PartialOrd, Debug)]
+struct i32x4(i32, i32, i32, i32);
+
+fn main() {
+    let a = i32x4(1, 2, 3, 4);
+    let b = i32x4(1, 2, 3, 5);
+    if a == b {
+        panic!();
+    }
+    assert!(a != b);
+    assert!(a < b);
+}
@@ -0,0 +1,87 @@
+// Comparing or doing arithmetic on SIMD vectors of different shapes, or a vector and a scalar,
+// reports the lanes of both operands.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd)]
+
+use std::ops;
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq)]
+struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq)]
+struct i16x4(i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq)]
+struct f32x4(f32, f32, f32, f32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct i32x4(i32, i32, i32, i32);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct u8x4(u8, u8, u8, u8);
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct u8x8(u8, u8, u8, u8, u8, u8, u8, u8);
+
+impl ops::Add for i16x8 {
+    type Output = i16x8;
+
+    fn add(self, rhs: i16x8) -> i16x8 {
+        rhs
+    }
+}
+
+impl ops::Add for f32x4 {
+    type Output = f32x4;
+
+    fn add(self, rhs: f32x4) -> f32x4 {
+        rhs
+    }
+}
+
+impl ops::BitAnd for u8x4 {
+    type Output = u8x4;
+
+    fn bitand(self, rhs: u8x4) -> u8x4 {
+        rhs
+    }
+}
+
+impl ops::BitAnd for u8x8 {
+    type Output = u8x8;
+
+    fn bitand(self, rhs: u8x8) -> u8x8 {
+        rhs
+    }
+}
+
+fn compare(a: i16x8, b: i16x4, c: f32x4, x: i16) {
+    let _ = a == b;
+    //~^ ERROR cannot compare `i16x8` (8 lanes of i16) with `i16x4` (4 lanes of i16) [E0308]
+    let _ = b == c;
+    //~^ ERROR cannot compare `i16x4` (4 lanes of i16) with `f32x4` (4 lanes of f32) [E0308]
+    let _ = a == x;
+    //~^ ERROR cannot compare `i16x8` (8 lanes of i16) with `i16` [E0308]
+    let _ = x == a;
+    //~^ ERROR cannot compare `i16` with `i16x8` (8 lanes of i16) [E0308]
+}
+
+fn arithmetic(a: i16x8, b: i16x4, c: f32x4, d: i32x4, e: u8x4, f: u8x8) {
+    let _ = a + b;
+    //~^ ERROR cannot apply `+` to `i16x8` (8 lanes of i16) and `i16x4` (4 lanes of i16) [E0308]
+    let _ = c + d;
+    //~^ ERROR cannot apply `+` to `f32x4` (4 lanes of f32) and `i32x4` (4 lanes of i32) [E0308]
+    let _ = c + a;
+    //~^ ERROR cannot apply `+` to `f32x4` (4 lanes of f32) and `i16x8` (8 lanes of i16) [E0308]
+    let _ = f & e;
+    //~^ ERROR cannot apply `&` to `u8x8` (8 lanes of u8) and `u8x4` (4 lanes of u8) [E0308]
+}
+
+fn main() {}
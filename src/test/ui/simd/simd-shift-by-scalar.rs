@@ -0,0 +1,38 @@
+// run-pass
+// Integer vectors can be shifted by a scalar amount, which shifts every lane by that amount.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd, simd_shift_by_scalar)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i16x8(i16, i16, i16, i16, i16, i16, i16, i16);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u8x4(u8, u8, u8, u8);
+
+fn main() {
+    let v = u32x4(1, 2, 3, 0x8000_0000);
+    assert_eq!(v << 3, u32x4(8, 16, 24, 0));
+    assert_eq!(v << 3u8, u32x4(8, 16, 24, 0));
+    assert_eq!(v >> 1, u32x4(0, 1, 1, 0x4000_0000));
+
+    let mut w = v;
+    w <<= 4;
+    assert_eq!(w, u32x4(16, 32, 48, 0));
+    w >>= 4i64;
+    assert_eq!(w, u32x4(1, 2, 3, 0));
+
+    // Right shifts of signed lanes are arithmetic.
+    let s = i16x8(-16, -1, 0, 1, 16, i16::MAX, i16::MIN, 7);
+    assert_eq!(s >> 2, i16x8(-4, -1, 0, 0, 4, i16::MAX >> 2, i16::MIN >> 2, 1));
+
+    // The shift amount is converted to the width of the lanes.
+    let b = u8x4(1, 2, 4, 0x80);
+    assert_eq!(b << 1u64, u8x4(2, 4, 8, 0));
+}
@@ -0,0 +1,14 @@
+// Shifting integer vectors can't be evaluated at compile time, which is reported as unsupported.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd, simd_shift_by_scalar)]
+
+#[repr(simd)]
+#[derive(Copy, Clone)]
+struct u32x4(u32, u32, u32, u32);
+
+const V: u32x4 = u32x4(1, 2, 3, 4);
+pub const W: u32x4 = V << 1;
+//~^ ERROR any use of this value will cause an error
+
+fn main() {}
@@ -0,0 +1,39 @@
+// run-pass
+// compile-flags: -C debug_assertions=yes
+// ignore-wasm32-bare compiled with panic=abort by default
+// ignore-emscripten no processes
+// With overflow checks, shifting an integer vector by at least the bit width of its lanes, or by a
+// negative amount, panics like shifting a single lane would.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd, simd_shift_by_scalar)]
+
+use std::panic;
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct i8x4(i8, i8, i8, i8);
+
+fn shl(v: u32x4, n: i32) -> u32x4 {
+    v << n
+}
+
+fn shr(v: i8x4, n: u64) -> i8x4 {
+    v >> n
+}
+
+fn main() {
+    let v = u32x4(1, 2, 3, 4);
+    assert_eq!(shl(v, 31), u32x4(1 << 31, 0, 1 << 31, 0));
+    assert!(panic::catch_unwind(|| shl(v, 32)).is_err());
+    assert!(panic::catch_unwind(|| shl(v, -1)).is_err());
+
+    let s = i8x4(-128, -1, 0, 127);
+    assert_eq!(shr(s, 7), i8x4(-1, -1, 0, 0));
+    assert!(panic::catch_unwind(|| shr(s, 8)).is_err());
+    assert!(panic::catch_unwind(|| shr(s, u64::MAX)).is_err());
+}
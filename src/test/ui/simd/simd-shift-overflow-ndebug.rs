@@ -0,0 +1,26 @@
+// run-pass
+// compile-flags: -C debug_assertions=no
+// Without overflow checks, the amount that an integer vector is shifted by is masked to the bit
+// width of its lanes, like the amount that a single lane is shifted by.
+
+#![allow(non_camel_case_types)]
+#![feature(repr_simd, simd_shift_by_scalar)]
+
+#[repr(simd)]
+#[derive(Copy, Clone, PartialEq, Debug)]
+struct u32x4(u32, u32, u32, u32);
+
+fn shl(v: u32x4, n: i32) -> u32x4 {
+    v << n
+}
+
+fn shr(v: u32x4, n: u32) -> u32x4 {
+    v >> n
+}
+
+fn main() {
+    let v = u32x4(1, 2, 3, 4);
+    assert_eq!(shl(v, 33), shl(v, 1));
+    assert_eq!(shl(v, -1), shl(v, 31));
+    assert_eq!(shr(v, 32), v);
+}
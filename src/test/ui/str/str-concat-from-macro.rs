@@ -0,0 +1,14 @@
+// String concatenations with operands written by macros explain how to fix them, instead of
+// suggesting to rewrite the output of the macros.
+
+fn main() {
+    let world = " World".to_owned();
+    let _ = concat!("Hello", ",") + " World";
+    //~^ ERROR cannot add `&str` to `&str`
+    let _ = "Hello," + stringify!(World);
+    //~^ ERROR cannot add `&str` to `&str`
+    let _ = &*world + "!";
+    //~^ ERROR cannot add `&str` to `&str`
+    let _ = stringify!(Hello) + world;
+    //~^ ERROR cannot add `std::string::String` to `&str`
+}
@@ -0,0 +1,13 @@
+// The alternatives to indexing a string by an integer start with the one of the expected type.
+
+fn initial(name: &str) -> char {
+    name[0]
+    //~^ ERROR the type `str` cannot be indexed by `{integer}`
+}
+
+fn prefix(name: &String) -> &str {
+    name[0]
+    //~^ ERROR the type `str` cannot be indexed by `{integer}`
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+// Indexing a string by an integer explains that strings are UTF-8 and how to read a byte or a
+// `char` instead.
+
+fn main() {
+    let _ = "hello"[0];
+    //~^ ERROR the type `str` cannot be indexed by `{integer}`
+    let name = String::from("ferris");
+    let i: usize = 2;
+    let _ = name[i];
+    //~^ ERROR the type `str` cannot be indexed by `usize`
+}
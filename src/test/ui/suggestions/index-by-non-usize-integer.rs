@@ -0,0 +1,18 @@
+// run-rustfix
+// Indexing a slice, array or `Vec` with an integer that isn't a `usize` suggests converting it.
+
+#![allow(unused_imports)]
+
+use std::convert::TryFrom;
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let a = [1, 2, 3];
+    let s = &v[..];
+    let (small, wide, long, signed): (u8, u32, u64, i32) = (0, 1, 2, 0);
+    let _ = v[small]; //~ ERROR cannot be indexed by `u8`
+    let _ = a[wide]; //~ ERROR cannot be indexed by `u32`
+    let _ = s[long]; //~ ERROR cannot be indexed by `u64`
+    let _ = v[signed]; //~ ERROR cannot be indexed by `i32`
+    let _ = a[wide - 1]; //~ ERROR cannot be indexed by `u32`
+}
@@ -0,0 +1,14 @@
+// Indexing with a negative integer literal explains how to index from the end instead.
+
+use std::collections::VecDeque;
+
+fn main() {
+    let v = vec![1, 2, 3];
+    let a = [1, 2, 3];
+    let s = &v[..];
+    let d: VecDeque<i32> = VecDeque::new();
+    let _ = v[-1]; //~ ERROR negative integers cannot be used to index on a `std::vec::Vec<
+    let _ = a[-2]; //~ ERROR negative integers cannot be used to index on a `[{integer}; 3]`
+    let _ = s[-1]; //~ ERROR negative integers cannot be used to index on a `&[{integer}]`
+    let _ = d[-1]; //~ ERROR negative integers cannot be used to index on a `std::collections::
+}
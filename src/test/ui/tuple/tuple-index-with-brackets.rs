@@ -0,0 +1,24 @@
+// Indexing a tuple or a tuple struct with `[]` explains how to access its elements instead.
+
+struct Point(i32, i32);
+
+mod opaque {
+    pub struct Opaque(i32, i32);
+
+    pub fn new() -> Opaque {
+        Opaque(0, 0)
+    }
+}
+
+fn main() {
+    let pair = (1, 2);
+    let mixed = (1, "one");
+    let point = Point(1, 2);
+    let i = 1;
+    let _ = pair[0]; //~ ERROR cannot index into a value of type
+    let _ = (&pair)[1]; //~ ERROR cannot index into a value of type
+    let _ = pair[2]; //~ ERROR cannot index into a value of type
+    let _ = mixed[i]; //~ ERROR cannot index into a value of type
+    let _ = point[1]; //~ ERROR cannot index into a value of type
+    let _ = opaque::new()[0]; //~ ERROR cannot index into a value of type
+}
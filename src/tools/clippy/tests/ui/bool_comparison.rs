@@ -112,7 +112,7 @@ fn issue3703() {
     if false < Foo {}
 }
 
-#[allow(dead_code)]
+#[allow(dead_code, negated_equality_operands)]
 fn issue4983() {
     let a = true;
     let b = false;
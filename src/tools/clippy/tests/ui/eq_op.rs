@@ -5,7 +5,7 @@
 #[allow(clippy::identity_op, clippy::double_parens, clippy::many_single_char_names)]
 #[allow(clippy::no_effect, unused_variables, clippy::unnecessary_operation, clippy::short_circuit_statement)]
 #[allow(clippy::nonminimal_bool)]
-#[allow(unused)]
+#[allow(unused, self_comparisons)]
 fn main() {
     // simple values and comparisons
     1 == 1;
@@ -1,3 +1,5 @@
+#![allow(unpredictable_function_pointer_comparisons)]
+
 use std::fmt::Debug;
 use std::ptr;
 use std::rc::Rc;